@@ -1,78 +1,330 @@
 
-use crate::map::Map;
+use crate::map::{Map, TourMode};
+use crate::params::AcoParams;
+use crate::schedule;
+use crate::vrp::Fleet;
 
-use cgmath::prelude::*;
 use rand::Rng;
 
 /// Represent a simple Ant!
 pub struct Ant {
-    /// The index of the city where the current
-    /// ant was.
+    /// The index of the city where the ant
+    /// start its tour.
     pub index: usize,
+
+    /// The index of the cities visited during the
+    /// last tour, in the order they are visited.
+    pub tour: Vec<usize>,
+
+    /// The length of the last tour.
+    pub length: f64,
 }
 
 impl Ant {
     /// Create new `Ant`
-    /// 
+    ///
     /// # Arguments
     /// * `index` - The index of the city where the ant must be started.
     pub fn new(index: usize) -> Ant {
-        Ant { index: index }
+        Ant {
+            index,
+            tour: Vec::new(),
+            length: 0.0,
+        }
+    }
+
+    /// Visit every city of the map once, starting from the city
+    /// at `index`, or from `start` to `end` when the path is fixed
+    /// (see `Map::mode`). The ant doesn't walk back to its first city,
+    /// the length of the path back is only counted when the tours are
+    /// closed. The tour and its length are stored in the ant. On a
+    /// vehicle routing problem, the ant walks a giant tour instead
+    /// (see `Ant::explore_routes`).
+    ///
+    /// When some cities have a time window, the ant keeps track of the
+    /// time and doesn't go to a city it would reach too late, unless
+    /// every unvisited city would be reached too late.
+    ///
+    /// The pheromones are not modified here, the caller must
+    /// deposit them once all ants completed their tour. The distance
+    /// to a city is weighted by the heuristic values of the map
+    /// (see `Map::set_beta`), not by `params.beta`.
+    ///
+    /// # Arguments
+    /// * `map` - A map.
+    /// * `params` - The colony parameters.
+    /// * `rng` - The random number generator used to choose the cities.
+    pub fn explore_map<R: Rng + ?Sized>(&mut self, map: &Map, params: &AcoParams, rng: &mut R) {
+        if let Some(fleet) = &map.fleet {
+            return self.explore_routes(map, fleet, params, rng)
+        }
+
+        let mut explored_city_indices = Vec::<usize>::with_capacity(map.cities.len());
+        let mut visited = vec![false; map.cities.len()];
+
+        let (mut current, end) = match map.mode {
+            TourMode::Fixed { start, end } => (start, Some(end)),
+            _ => (self.index, None),
+        };
+
+        explored_city_indices.push(current);
+        visited[current] = true;
+
+        // The end of a fixed path can't be chosen before the other cities...
+        let count = match end {
+            Some(end) => {
+                visited[end] = true;
+                map.cities.len() - 1
+            }
+            None => map.cities.len(),
+        };
+
+        let windows = map.has_time_windows();
+        let mut blocked = visited.clone();
+        let mut time = schedule::departure(&map.cities[current]);
+
+        while explored_city_indices.len() < count {
+            // The cities that would be reached too late can't be chosen...
+            if windows {
+                for (city, blocked) in blocked.iter_mut().enumerate() {
+                    *blocked = visited[city] || schedule::is_late(&map.cities[city], time + map.distance(current, city));
+                }
+            }
+
+            let choices = if windows && !blocked.iter().all(|&blocked| blocked) { &blocked } else { &visited };
+
+            let next = self.find_best_city_index(current, choices, map, params, rng);
+
+            explored_city_indices.push(next);
+            visited[next] = true;
+            time = schedule::service_start(&map.cities[next], time + map.distance(current, next)) + map.cities[next].service_time;
+            current = next;
+        }
+
+        explored_city_indices.extend(end);
+
+        self.length = map.tour_cost(&explored_city_indices);
+        self.tour = explored_city_indices;
     }
 
-    pub fn explore_map(&mut self, map: &mut Map) {
-        let mut explored_city_indices = Vec::<usize>::new();
+    /// Deliver every city of a vehicle routing problem, starting from
+    /// the depot. The ant goes back to the depot when no unvisited city
+    /// fits in the vehicle anymore, and starts a new route from there.
+    /// When some cities have a time window, a city is only chosen if
+    /// the vehicle reaches it in time and can still be back at the
+    /// depot in time. When no city can be reached in time from the depot
+    /// itself, one of the cities that fit is served late and the windows
+    /// are checked again from there. The giant tour (see `Fleet`) and its length are
+    /// stored in the ant.
+    ///
+    /// # Arguments
+    /// * `map` - A map.
+    /// * `fleet` - The vehicles.
+    /// * `params` - The colony parameters.
+    /// * `rng` - The random number generator used to choose the cities.
+    fn explore_routes<R: Rng + ?Sized>(&mut self, map: &Map, fleet: &Fleet, params: &AcoParams, rng: &mut R) {
+        let size = map.cities.len();
+        let mut tour = Vec::<usize>::with_capacity(size);
+        let mut visited = vec![false; size];
+        let mut overloaded = vec![false; size];
+        let mut blocked = vec![false; size];
+        let mut remaining = size - 1;
+        let mut load = 0.0;
+        let mut current = fleet.depot;
+        let depot = &map.cities[fleet.depot];
+        let mut time = schedule::departure(depot);
+        let windows = map.has_time_windows();
+
+        tour.push(current);
+        visited[current] = true;
+
+        while remaining > 0 {
+            // The cities that don't fit in the vehicle can't be chosen...
+            for (city, overloaded) in overloaded.iter_mut().enumerate() {
+                *overloaded = visited[city] || load + map.cities[city].demand > fleet.capacity;
+            }
+
+            blocked.copy_from_slice(&overloaded);
+
+            // ... nor the ones that would be reached too late.
+            if windows {
+                for (city, blocked) in blocked.iter_mut().enumerate().filter(|(_, blocked)| !**blocked) {
+                    let arrival = time + map.distance(current, city);
+                    let back = schedule::service_start(&map.cities[city], arrival) + map.cities[city].service_time;
 
-        explored_city_indices.push(self.index);
-        
-        while explored_city_indices.len() < map.cities.len() {
-            let random_city_index = self.find_best_city_index(&explored_city_indices, &map);
+                    *blocked = schedule::is_late(&map.cities[city], arrival)
+                        || schedule::is_late(depot, back + map.distance(city, fleet.depot));
+                }
+            }
+
+            let choices = if !blocked.iter().all(|&blocked| blocked) {
+                &blocked
+            } else if current == fleet.depot {
+                // No new route can serve the remaining cities in time, one of them is late.
+                &overloaded
+            } else {
+                tour.push(fleet.depot);
+                current = fleet.depot;
+                time = schedule::departure(depot);
+                load = 0.0;
+                continue
+            };
 
-            explored_city_indices.push(random_city_index);
-            map.pheromones[self.index][random_city_index] += 1;
-            self.index = random_city_index;
+            let next = self.find_best_city_index(current, choices, map, params, rng);
+
+            tour.push(next);
+            visited[next] = true;
+            load += map.cities[next].demand;
+            time = schedule::service_start(&map.cities[next], time + map.distance(current, next)) + map.cities[next].service_time;
+            current = next;
+            remaining -= 1;
         }
+
+        self.length = map.tour_cost(&tour);
+        self.tour = tour;
     }
 
     /// Search a city to go based on pheromones and some probabilites.
     /// It return the city index.
-    /// 
+    ///
+    /// When the map has candidate lists, only the unvisited candidates
+    /// of the current city are looked at. If they are all visited, the
+    /// most attractive unvisited city is chosen.
+    ///
     /// # Arguments
-    /// * `explored_cities_index` - An array that contains all cities index that the ant was already explored.
+    /// * `current` - The index of the city where the ant is.
+    /// * `visited` - `true` at the index of each city that the ant was already explored (or can't go to).
     /// * `map` - A map.
-    fn find_best_city_index(&self, explored_cities_index: &[usize], map: &Map) -> usize {
-
-        /* 
-        This array store the index of some cities.
-
-        The index of a city can be duplicate many time in
-        this array and the reason for that is that more a 
-        city index is duplicated more the index have a chance 
-        to be selected.
-        */
-        let mut cities_index = Vec::<usize>::new();
-
-        for i in 0..map.cities.len() {
-            // Don't include the city where the current Ant are and
-            // the already visited cities...
-            if i == self.index || explored_cities_index.contains(&i) {
-                continue
+    /// * `params` - The colony parameters.
+    /// * `rng` - The random number generator.
+    fn find_best_city_index<R: Rng + ?Sized>(
+        &self,
+        current: usize,
+        visited: &[bool],
+        map: &Map,
+        params: &AcoParams,
+        rng: &mut R,
+    ) -> usize {
+        let unvisited = (0..map.cities.len()).filter(|&i| !visited[i]);
+        let candidates = map.candidates(current);
+
+        if candidates.is_empty() {
+            return Ant::choose(current, unvisited, map, params, rng).unwrap_or(current)
+        }
+
+        let unvisited_candidates = candidates.iter().copied().filter(|&i| !visited[i]);
+
+        match Ant::choose(current, unvisited_candidates, map, params, rng) {
+            Some(city) => city,
+            None => Ant::most_attractive(current, unvisited, map, params).unwrap_or(current),
+        }
+    }
+
+    /// Choose a city among `cities`. With a probability of `params.exploitation()`
+    /// the most attractive city is chosen (exploitation), otherwise a
+    /// city is drawn with a probability proportional to its attraction
+    /// (exploration). Returns `None` if `cities` is empty.
+    ///
+    /// # Arguments
+    /// * `current` - The index of the city where the ant is.
+    /// * `cities` - The index of the cities where the ant may go.
+    /// * `map` - A map.
+    /// * `params` - The colony parameters.
+    /// * `rng` - The random number generator.
+    fn choose<I, R>(current: usize, cities: I, map: &Map, params: &AcoParams, rng: &mut R) -> Option<usize>
+    where
+        I: Iterator<Item = usize> + Clone,
+        R: Rng + ?Sized,
+    {
+        // Sum the attraction of every city and keep
+        // track of the most attractive one...
+        let mut total = 0.0;
+        let mut count = 0;
+        let (mut best, mut best_index) = (-1.0, None);
+
+        for i in cities.clone() {
+            let attraction = Ant::attraction(current, i, map, params);
+
+            if attraction > best {
+                best = attraction;
+                best_index = Some(i);
             }
 
-            // Get the distance from the current city (where the current ant are)
-            // to another city.
-            let distance = (map.cities[i].position - map.cities[self.index].position).magnitude();
+            total += attraction;
+            count += 1;
+        }
 
-            // Calculate the probability that the ant is attracted to this city.
-            let prob = (1.0 / distance).powf(2.3) * (map.pheromones[self.index][i] as f32).powf(1.1);
+        if count == 0 {
+            return None
+        }
 
-            // More prob value is higher more the current city index was duplicated.
-            for _ in 0..=(prob as usize) {
-                cities_index.push(i)
+        let q0 = params.exploitation();
+
+        if q0 > 0.0 && rng.gen::<f64>() < q0 {
+            return best_index
+        }
+
+        // A city at the same position than the current one is
+        // infinitely attractive...
+        if !total.is_finite() {
+            return best_index
+        }
+
+        // Every attraction underflowed to zero, so all cities
+        // are equally attractive.
+        if total <= 0.0 {
+            return cities.clone().nth(rng.gen_range(0..count))
+        }
+
+        // Roulette wheel: walk the cumulative attractions until
+        // the random threshold is reached.
+        let mut threshold = rng.gen::<f64>() * total;
+        let mut last_index = best_index;
+
+        for i in cities {
+            threshold -= Ant::attraction(current, i, map, params);
+            last_index = Some(i);
+
+            if threshold < 0.0 {
+                return last_index
             }
         }
 
-        println!("{}", cities_index.len());
-        cities_index[rand::thread_rng().gen_range(0..cities_index.len())]
+        // Rounding errors may keep the threshold slightly above zero.
+        last_index
     }
-}
\ No newline at end of file
+
+    /// Returns the most attractive city among `cities`,
+    /// or `None` if `cities` is empty.
+    ///
+    /// # Arguments
+    /// * `current` - The index of the city where the ant is.
+    /// * `cities` - The index of the cities where the ant may go.
+    /// * `map` - A map.
+    /// * `params` - The colony parameters.
+    fn most_attractive<I: Iterator<Item = usize>>(current: usize, cities: I, map: &Map, params: &AcoParams) -> Option<usize> {
+        let mut best = None;
+
+        for i in cities {
+            let attraction = Ant::attraction(current, i, map, params);
+
+            match best {
+                Some((best_attraction, _)) if best_attraction >= attraction => {}
+                _ => best = Some((attraction, i)),
+            }
+        }
+
+        best.map(|(_, i)| i)
+    }
+
+    /// Returns how much an ant is attracted by the path between two cities.
+    ///
+    /// # Arguments
+    /// * `from` - The index of the city where the ant is.
+    /// * `to` - The index of the city where the ant may go.
+    /// * `map` - A map.
+    /// * `params` - The colony parameters.
+    fn attraction(from: usize, to: usize, map: &Map, params: &AcoParams) -> f64 {
+        map.heuristic(from, to) * map.pheromones[from][to].powf(params.alpha)
+    }
+}
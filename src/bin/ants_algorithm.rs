@@ -1,48 +1,449 @@
-//! My crate opengl_rs is awesome
-extern crate gl;
-extern crate glfw;
+//! Solve a traveling salesman problem with an ant colony.
+//!
+//! Run `ants_algorithm --help` to see the options.
 
-use cgmath::Vector2;
-use luve_rust::{ant::Ant, city::City, map::Map};
+use luve_rust::bounds::{held_karp_bound, mst_bound, one_tree_bound};
+use luve_rust::colony::{Colony, Termination};
+use luve_rust::construction::{cheapest_insertion, double_tree, farthest_insertion, greedy_edge, nearest_neighbour};
+use luve_rust::instance::load_instance;
+use luve_rust::local_search::LocalSearchScope;
+use luve_rust::map::{Map, TourMode};
+use luve_rust::observer::{Control, Observer};
+use luve_rust::params::AcoParams;
+use luve_rust::stats::IterationStats;
+use luve_rust::strategy::{AcsParams, ElitistParams, MaxMinParams, RankBasedParams, Strategy};
+use luve_rust::tsplib::save_tour;
+use luve_rust::vrp;
+
+use std::process;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// The arguments are invalid.
+const EXIT_USAGE: i32 = 2;
+
+/// The instance can't be loaded or is invalid.
+const EXIT_INSTANCE: i32 = 3;
+
+/// An output file can't be written.
+const EXIT_OUTPUT: i32 = 4;
+
+const USAGE: &str = "\
+Usage: ants_algorithm [OPTIONS] <INSTANCE>
+
+Solve a traveling salesman problem with an ant colony. The instance is
+a TSPLIB problem file (TSP, ATSP or CVRP), a Solomon VRPTW file,
+a .csv file of `x,y` lines, or a .json file with an array of `[x, y]`
+cities (or an object with a `cities` array). The routes of a vehicle
+routing instance are written as a single tour that goes back to the
+depot between two routes. When the cities have time windows, the
+length includes a penalty for each unit of lateness.
+
+Algorithm:
+  -a, --algorithm <NAME>   as, elitist, rank, mmas or acs [default: as]
+      --ants <N>           Number of ants [default: one per city]
+      --alpha <X>          Pheromone weight [default: 1.1]
+      --beta <X>           Distance weight [default: 2.3]
+      --rho <X>            Evaporation rate [default: 0.5]
+      --q <X>              Pheromones deposited by an ant [default: 1]
+      --q0 <X>             Probability to take the most attractive city [default: 0, 0.9 with acs]
+      --candidates <N>     Candidate list size, 0 for every city [default: 20]
+      --mode <MODE>        closed (back to the first city), open (a path with free
+                           ends) or START-END (a path between two cities, numbered
+                           from 0) [default: closed]
+      --local-search <S>   none, best (iteration best) or all [default: none]
+      --lateness-penalty <X>
+                           Cost of each unit of lateness with time windows [default: 100]
+      --threads <N>        Threads used to build the tours, 0 for every core [default: 1]
+      --seed <N>           Seed of the random number generators [default: random]
+
+Termination (the first one reached stops the run):
+  -i, --iterations <N>     Maximum number of iterations [default: 100]
+  -t, --time-limit <SECS>  Maximum running time
+      --target <LENGTH>    Stop once a tour this short is found
+      --stagnation <N>     Stop after N iterations without improvement
+
+Output:
+  -o, --tour <FILE>        Write the best tour to a TSPLIB tour file
+      --stats <FILE>       Write the statistics of each iteration (.csv or .json)
+      --lower-bound <B>    Report the gap to a lower bound: none, mst, 1tree or hk
+                           (Held-Karp, slow on large instances) [default: none]
+      --baseline           Report the length of the construction heuristics
+                           (TSP and ATSP instances without fixed endpoints,
+                           not with --quiet)
+  -q, --quiet              Only print the length of the best tour
+  -v, --verbose            Print the progress of each iteration
+  -h, --help               Print this help
+
+Exit codes: 0 on success, 2 for invalid arguments, 3 when the
+instance can't be loaded or is invalid and 4 when an output can't
+be written.";
+
+/// How much is printed.
+#[derive(Clone, Copy, PartialEq)]
+enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+/// The options given on the command line.
+struct Options {
+    instance: String,
+    algorithm: String,
+    ants: Option<usize>,
+    q0: Option<f64>,
+    local_search: LocalSearchScope,
+    mode: TourMode,
+    tour: Option<String>,
+    stats: Option<String>,
+    lower_bound: String,
+    baseline: bool,
+    lateness_penalty: Option<f64>,
+    verbosity: Verbosity,
+    params: AcoParams,
+}
+
+/// Print the progress of the colony on the standard error.
+struct Progress {
+    verbosity: Verbosity,
+}
+
+impl Observer for Progress {
+    fn iteration_ended(&mut self, _colony: &Colony, stats: &IterationStats) -> Control {
+        if self.verbosity == Verbosity::Verbose {
+            eprintln!(
+                "iteration {:>6}  best {:>14.2}  mean {:>14.2}  global {:>14.2}  branching {:>6.2}{}",
+                stats.iteration,
+                stats.best_length,
+                stats.mean_length,
+                stats.global_best,
+                stats.branching_factor,
+                if stats.reset { "  (reset)" } else { "" },
+            );
+        }
+
+        Control::Continue
+    }
+
+    fn new_best(&mut self, colony: &Colony) -> Control {
+        if self.verbosity == Verbosity::Normal {
+            eprintln!("iteration {:>6}  new best {:.2}", colony.iteration, colony.best_length);
+        }
+
+        Control::Continue
+    }
+}
 
 pub fn main() {
-    
-    // Create new map with cities...
-    let mut map = Map::new(&[
-        City::new(Vector2::new(0.0, 0.0)),
-        City::new(Vector2::new(0.0, 1.0)),
-        City::new(Vector2::new(1.0, 1.0)),
-        City::new(Vector2::new(2.0, 0.0)),
-    ]);
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-    // Generate ants (one per city)
-    let mut ants = Vec::new();
+    let options = match parse_args(&args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return
+        }
+        Err(message) => {
+            eprintln!("error: {}\n\nRun `ants_algorithm --help` to see the options.", message);
+            process::exit(EXIT_USAGE);
+        }
+    };
 
-    for i in 0..map.cities.len() {
-        ants.push(Ant::new(i));
+    if let Err((code, message)) = run(options) {
+        eprintln!("error: {}", message);
+        process::exit(code);
     }
+}
+
+/// Load the instance, let the ants explore it and write the outputs.
+fn run(mut options: Options) -> Result<(), (i32, String)> {
+    let mut instance = load_instance(&options.instance)
+        .map_err(|e| (EXIT_INSTANCE, format!("can't load `{}`: {}", options.instance, e.message)))?;
 
-    // Spawn 100 x 4 ants to explore the map
-    for _ in 0..100 {
-        for i in 0..ants.len() {
-            ants[i].explore_map(&mut map)
+    // The tour mode is given on the command line, the rest of the map is checked by the colony.
+    if let TourMode::Fixed { start, end } = options.mode {
+        if start == end || start.max(end) >= instance.map.cities.len() {
+            let count = instance.map.cities.len();
+            return Err((EXIT_USAGE, format!("invalid mode: the cities must differ and be below {}", count)));
         }
     }
 
-    // Retrieve the best path from the first city (at index 0)
-    let best_path = map.best_path(3);
+    instance.map.mode = options.mode;
+
+    // The heuristics build a single tour through every city, with no depot and no fixed end.
+    if options.baseline && (instance.map.fleet.is_some() || matches!(options.mode, TourMode::Fixed { .. })) {
+        return Err((EXIT_USAGE, String::from("`--baseline` needs a TSP instance without fixed endpoints")));
+    }
+
+    if let Some(penalty) = options.lateness_penalty {
+        instance.map.lateness_penalty = penalty;
+    }
+
+    let city_count = instance.map.cities.len();
+    let params = &mut options.params;
+
+    params.ant_count = options.ants.unwrap_or(city_count);
+    params.local_search.scope = options.local_search;
+    params.strategy = match options.algorithm.as_str() {
+        "as" => Strategy::AntSystem,
+        "elitist" | "eas" => Strategy::Elitist(ElitistParams { weight: city_count as f64 }),
+        "rank" | "ras" => Strategy::RankBased(RankBasedParams::default()),
+        "mmas" => Strategy::MaxMin(MaxMinParams::default()),
+        "acs" => Strategy::AntColonySystem(AcsParams {
+            q0: options.q0.unwrap_or(AcsParams::default().q0),
+            ..AcsParams::default()
+        }),
+        name => return Err((EXIT_USAGE, format!("unknown algorithm `{}`", name))),
+    };
+
+    if let Some(q0) = options.q0 {
+        params.q0 = q0;
+    }
+
+    let lower_bound = match options.lower_bound.as_str() {
+        "none" => None,
+        "mst" => Some(mst_bound(&instance.map)),
+        "1tree" => Some(one_tree_bound(&instance.map)),
+        "hk" => Some(held_karp_bound(&instance.map, 1000)),
+        name => return Err((EXIT_USAGE, format!("unknown lower bound `{}`", name))),
+    };
+
+    // The map is checked by the colony too, its errors come from the instance.
+    let path = &options.instance;
+    let mut colony = Colony::new(instance.map, options.params).map_err(|e| match e.kind.as_str() {
+        "map" => (EXIT_INSTANCE, format!("invalid instance `{}`: {}", path, e.message)),
+        _ => (EXIT_USAGE, format!("invalid parameter {}: {}", e.kind, e.message)),
+    })?;
+
+    if options.verbosity != Verbosity::Quiet {
+        eprintln!(
+            "{}: {} cities, {} ants, seed {}",
+            instance.name,
+            city_count,
+            colony.ants.len(),
+            colony.seed()
+        );
+        colony.add_observer(Box::new(Progress { verbosity: options.verbosity }));
+    }
+
+    colony.lower_bound = lower_bound;
+
+    let result = colony.run();
+
+    if let Some(path) = &options.tour {
+        let comment = format!("Length {}", result.best_length);
+        save_tour(path, &instance.name, &comment, &result.best_tour)
+            .map_err(|e| (EXIT_OUTPUT, format!("can't write `{}`: {}", path, e.message)))?;
+    }
+
+    if let Some(path) = &options.stats {
+        let saved = if path.to_ascii_lowercase().ends_with(".json") {
+            result.history.save_json(path)
+        } else {
+            result.history.save_csv(path)
+        };
 
-    // Print the result !!
-    print!("Path : ");
-    for i in 0..best_path.len() {
-        print!(
-            "city {}{}",
-            best_path[i],
-            if i < best_path.len() - 1 {
-                " to "
+        saved.map_err(|e| (EXIT_OUTPUT, format!("can't write `{}`: {}", path, e)))?;
+    }
+
+    if options.verbosity == Verbosity::Quiet {
+        println!("{}", result.best_length);
+    } else {
+        let termination = match result.termination {
+            Some(Termination::Cancelled) => "cancelled",
+            Some(Termination::Iterations) => "iteration limit",
+            Some(Termination::TimeLimit) => "time limit",
+            Some(Termination::TargetLength) => "target length reached",
+            Some(Termination::Stagnation) => "stagnation",
+            None => "unknown",
+        };
+
+        eprintln!(
+            "stopped after {} iterations ({}) in {:.3}s",
+            result.iterations,
+            termination,
+            colony.elapsed().as_secs_f64()
+        );
+
+        // Print the result !!
+        let path: Vec<String> = result.best_tour.iter().map(|city| city.to_string()).collect();
+        println!("Path : {}", path.join(" "));
+        println!("Length : {}", result.best_length);
+
+        if let Some(fleet) = &colony.map.fleet {
+            for (i, route) in vrp::routes(&result.best_tour, fleet.depot).iter().enumerate() {
+                let cities: Vec<String> = route.iter().map(|city| city.to_string()).collect();
+                println!("Route {} : {} (load {})", i + 1, cities.join(" "), vrp::load(&colony.map, route));
+            }
+        }
+
+        if let Some(schedule) = &result.schedule {
+            let late = schedule.late_cities();
+
+            println!("Distance : {}", colony.map.tour_length(&result.best_tour));
+            println!("Waiting : {:.2}", schedule.waiting);
+
+            if schedule.is_feasible() {
+                println!("Lateness : 0 (feasible)");
             } else {
-                "\n"
+                println!("Lateness : {:.2} ({} cities served late)", schedule.lateness, late.len());
+            }
+        }
+
+        if let (Some(bound), Some(gap)) = (result.lower_bound, result.gap) {
+            println!("Lower bound : {:.2} (gap {:.2}%)", bound, gap);
+        }
+
+        if options.baseline {
+            for (name, tour) in baselines(&colony.map) {
+                let length = colony.map.tour_length(&tour);
+                let gap = (result.best_length - length) / length * 100.0;
+                println!("{} : {:.2} (colony {:+.2}%)", name, length, gap);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the tours of the construction heuristics, with their names.
+///
+/// # Arguments
+/// * `map` - The map of the colony.
+fn baselines(map: &Map) -> Vec<(&'static str, Vec<usize>)> {
+    vec![
+        ("Nearest neighbour", nearest_neighbour(map, 0)),
+        ("Greedy edge", greedy_edge(map)),
+        ("Cheapest insertion", cheapest_insertion(map)),
+        ("Farthest insertion", farthest_insertion(map)),
+        ("Double tree", double_tree(map)),
+    ]
+}
+
+/// Parse the command line arguments. Returns `None` when the help is asked.
+fn parse_args(args: &[String]) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        instance: String::new(),
+        algorithm: String::from("as"),
+        ants: None,
+        q0: None,
+        local_search: LocalSearchScope::None,
+        mode: TourMode::Closed,
+        tour: None,
+        stats: None,
+        lower_bound: String::from("none"),
+        baseline: false,
+        lateness_penalty: None,
+        verbosity: Verbosity::Normal,
+        params: AcoParams::default(),
+    };
+
+    let mut instance = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        // Accept `--option=value` as well as `--option value`.
+        let (name, inline) = match arg.find('=') {
+            Some(index) if arg.starts_with("--") => (&arg[..index], Some(arg[index + 1..].to_string())),
+            _ => (arg.as_str(), None),
+        };
+
+        let mut value = || -> Result<String, String> {
+            match &inline {
+                Some(value) => Ok(value.clone()),
+                None => args.next().cloned().ok_or_else(|| format!("`{}` needs a value", name)),
+            }
+        };
+
+        let params = &mut options.params;
+
+        match name {
+            "-h" | "--help" => return Ok(None),
+            "-q" | "--quiet" => options.verbosity = Verbosity::Quiet,
+            "-v" | "--verbose" => options.verbosity = Verbosity::Verbose,
+            "-a" | "--algorithm" => options.algorithm = value()?.to_ascii_lowercase(),
+            "--ants" => options.ants = Some(parse(name, &value()?)?),
+            "--alpha" => params.alpha = parse(name, &value()?)?,
+            "--beta" => params.beta = parse(name, &value()?)?,
+            "--rho" => params.rho = parse(name, &value()?)?,
+            "--q" => params.q = parse(name, &value()?)?,
+            "--q0" => options.q0 = Some(parse(name, &value()?)?),
+            "--candidates" => params.candidates = parse(name, &value()?)?,
+            "--threads" => params.threads = parse(name, &value()?)?,
+            "--seed" => params.seed = Some(parse(name, &value()?)?),
+            "-i" | "--iterations" => params.iterations = parse(name, &value()?)?,
+            "-t" | "--time-limit" => {
+                let seconds: f64 = parse(name, &value()?)?;
+
+                if !(seconds >= 0.0 && seconds.is_finite()) {
+                    return Err(format!("invalid value for `{}`: `{}`", name, seconds));
+                }
+
+                params.time_limit = Some(Duration::from_secs_f64(seconds));
+            }
+            "--target" => params.target_length = Some(parse(name, &value()?)?),
+            "--stagnation" => params.stagnation_limit = Some(parse(name, &value()?)?),
+            "--mode" => {
+                let mode = value()?.to_ascii_lowercase();
+
+                options.mode = match mode.as_str() {
+                    "closed" => TourMode::Closed,
+                    "open" => TourMode::Open,
+                    _ => match mode.split_once('-') {
+                        Some((start, end)) => TourMode::Fixed {
+                            start: parse(name, start)?,
+                            end: parse(name, end)?,
+                        },
+                        None => return Err(format!("unknown mode `{}`", mode)),
+                    },
+                };
+            }
+            "--local-search" => {
+                options.local_search = match value()?.as_str() {
+                    "none" | "off" => LocalSearchScope::None,
+                    "best" => LocalSearchScope::IterationBest,
+                    "all" | "on" => LocalSearchScope::EveryAnt,
+                    other => return Err(format!("unknown local search `{}`", other)),
+                };
             }
-        )
+            "--lateness-penalty" => {
+                let penalty: f64 = parse(name, &value()?)?;
+
+                if !(penalty >= 0.0 && penalty.is_finite()) {
+                    return Err(format!("invalid value for `{}`: `{}`", name, penalty));
+                }
+
+                options.lateness_penalty = Some(penalty);
+            }
+            "-o" | "--tour" => options.tour = Some(value()?),
+            "--stats" => options.stats = Some(value()?),
+            "--lower-bound" => options.lower_bound = value()?.to_ascii_lowercase(),
+            "--baseline" => options.baseline = true,
+            _ if name.starts_with('-') && name.len() > 1 => return Err(format!("unknown option `{}`", name)),
+            _ => {
+                if instance.is_some() {
+                    return Err(format!("unexpected argument `{}`", arg));
+                }
+
+                instance = Some(arg.clone());
+            }
+        }
     }
+
+    options.instance = instance.ok_or_else(|| String::from("the instance file is missing"))?;
+
+    // The quiet output is a single length, there is no room for the heuristics.
+    if options.baseline && options.verbosity == Verbosity::Quiet {
+        return Err(String::from("`--baseline` can't be used with `--quiet`"));
+    }
+
+    Ok(Some(options))
+}
+
+/// Parse the value of an option.
+fn parse<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse::<T>()
+        .map_err(|_| format!("invalid value for `{}`: `{}`", name, value))
 }
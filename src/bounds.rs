@@ -0,0 +1,200 @@
+use crate::construction;
+use crate::map::{Map, TourMode};
+
+/// Bound improvements smaller than this are ignored.
+const EPSILON: f64 = 1e-9;
+
+/// Returns the length of a minimum spanning tree of the cities. A tour
+/// without one of its paths is a spanning tree, so no tour is shorter. The
+/// routes of a vehicle routing problem contain a spanning tree too.
+///
+/// The bounds of this module ignore the direction of the paths: on an
+/// asymmetric map, a path is as long as its shorter direction.
+///
+/// # Arguments
+/// * `map` - A map.
+pub fn mst_bound(map: &Map) -> f64 {
+    let parent = construction::minimum_spanning_tree(map);
+
+    parent
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(city, &parent)| undirected_distance(map, city, parent))
+        .sum()
+}
+
+/// Returns the length of a minimum 1-tree: a spanning tree of every
+/// city but the first one, plus the two shortest paths from the first
+/// city. A tour is a 1-tree, so no tour is shorter. Neither a path nor
+/// the routes of a vehicle routing problem are a 1-tree, the MST bound
+/// is returned when the tours aren't closed or the map has a fleet.
+///
+/// # Arguments
+/// * `map` - A map.
+pub fn one_tree_bound(map: &Map) -> f64 {
+    let n = map.cities.len();
+
+    if map.mode != TourMode::Closed || map.fleet.is_some() {
+        return mst_bound(map)
+    }
+
+    if n < 3 {
+        return map.tour_length(&(0..n).collect::<Vec<usize>>())
+    }
+
+    one_tree(n, |i, j| undirected_distance(map, i, j)).0
+}
+
+/// Returns the Held-Karp bound: the 1-tree bound raised with subgradient
+/// optimization. A penalty is added to both ends of the paths of each city,
+/// which doesn't change which tour is the shortest, and updated until most
+/// cities have two paths in the 1-tree. It is usually within a few percent
+/// of the optimal tour, but each iteration takes `O(n^2)`. Like the 1-tree
+/// bound, it falls back to the MST bound when the tours aren't closed or
+/// the map has a fleet.
+///
+/// # Arguments
+/// * `map` - A map.
+/// * `iterations` - The maximum number of subgradient iterations.
+pub fn held_karp_bound(map: &Map, iterations: usize) -> f64 {
+    let n = map.cities.len();
+
+    if map.mode != TourMode::Closed || map.fleet.is_some() {
+        return mst_bound(map)
+    }
+
+    if n < 3 {
+        return map.tour_length(&(0..n).collect::<Vec<usize>>())
+    }
+
+    // The step size is based on how far the bound is from a known tour.
+    let upper = map.tour_length(&construction::nearest_neighbour(map, 0));
+    let period = (n / 4).max(5);
+
+    let mut penalties = vec![0.0; n];
+    let mut best = one_tree_bound(map);
+    let mut scale = 2.0;
+    let mut stalled = 0;
+
+    for _ in 0..iterations {
+        let (length, degrees) = one_tree(n, |i, j| undirected_distance(map, i, j) + penalties[i] + penalties[j]);
+        let bound = length - 2.0 * penalties.iter().sum::<f64>();
+
+        if bound > best + EPSILON {
+            best = bound;
+            stalled = 0;
+        } else {
+            stalled += 1;
+
+            if stalled >= period {
+                scale /= 2.0;
+                stalled = 0;
+            }
+        }
+
+        let norm: f64 = degrees.iter().map(|&degree| (degree as f64 - 2.0).powi(2)).sum();
+
+        // Every city has two paths, the 1-tree is a tour...
+        if norm == 0.0 {
+            break
+        }
+
+        let step = scale * (upper - bound).max(EPSILON) / norm;
+
+        if step < EPSILON {
+            break
+        }
+
+        for (penalty, &degree) in penalties.iter_mut().zip(degrees.iter()) {
+            *penalty += step * (degree as f64 - 2.0);
+        }
+    }
+
+    best
+}
+
+/// Returns the percentage by which `length` exceeds `bound`.
+///
+/// # Arguments
+/// * `length` - The length of a tour.
+/// * `bound` - A lower bound of the length of the tours.
+pub fn gap(length: f64, bound: f64) -> f64 {
+    100.0 * (length - bound) / bound
+}
+
+/// Returns the shorter of the distances from `i` to `j` and from `j` to `i`.
+fn undirected_distance(map: &Map, i: usize, j: usize) -> f64 {
+    map.distance(i, j).min(map.distance(j, i))
+}
+
+/// Returns the length of a minimum 1-tree and the number of paths of
+/// each city in it, with `cost` giving the length of a path.
+fn one_tree<F: Fn(usize, usize) -> f64>(n: usize, cost: F) -> (f64, Vec<usize>) {
+    let mut degrees = vec![0; n];
+    let mut length = 0.0;
+
+    if n < 3 {
+        return (length, degrees)
+    }
+
+    // Prim's algorithm on every city but the first one...
+    let mut in_tree = vec![false; n];
+    let mut distance = vec![f64::INFINITY; n];
+    let mut parent = vec![1; n];
+    distance[1] = 0.0;
+
+    for _ in 1..n {
+        let mut city = 0;
+
+        for other in 1..n {
+            if !in_tree[other] && (city == 0 || distance[other] < distance[city]) {
+                city = other;
+            }
+        }
+
+        in_tree[city] = true;
+
+        if city != 1 {
+            length += distance[city];
+            degrees[city] += 1;
+            degrees[parent[city]] += 1;
+        }
+
+        for other in 1..n {
+            if !in_tree[other] {
+                let d = cost(city, other);
+
+                if d < distance[other] {
+                    distance[other] = d;
+                    parent[other] = city;
+                }
+            }
+        }
+    }
+
+    // ... and the two shortest paths from the first city.
+    let (mut first, mut second) = (1, 2);
+
+    if cost(0, second) < cost(0, first) {
+        std::mem::swap(&mut first, &mut second);
+    }
+
+    for other in 3..n {
+        let d = cost(0, other);
+
+        if d < cost(0, first) {
+            second = first;
+            first = other;
+        } else if d < cost(0, second) {
+            second = other;
+        }
+    }
+
+    length += cost(0, first) + cost(0, second);
+    degrees[0] = 2;
+    degrees[first] += 1;
+    degrees[second] += 1;
+
+    (length, degrees)
+}
@@ -0,0 +1,568 @@
+use crate::city::City;
+use crate::colony::Colony;
+use crate::local_search::{LocalSearch, LocalSearchScope};
+use crate::map::{Map, TourMode};
+use crate::matrix::SquareMatrix;
+use crate::params::AcoParams;
+use crate::schedule::TimeWindow;
+use crate::stats::{History, IterationStats};
+use crate::strategy::{AcsParams, ElitistParams, MaxMinParams, RankBasedParams, Strategy};
+use crate::tsplib::{parse_number, read_numbers, split_keyword, TsplibError};
+use crate::vrp::Fleet;
+
+use cgmath::Vector2;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A checkpoint error.
+#[derive(Debug)]
+pub struct CheckpointError {
+    /// Kind of error.
+    pub kind: String,
+
+    /// The error description.
+    pub message: String,
+}
+
+impl CheckpointError {
+    /// Create new checkpoint error.
+    ///
+    /// # Arguments
+    /// * `kind` - The kind of error.
+    /// * `message` - The error description.
+    pub fn new(kind: &str, message: &str) -> CheckpointError {
+        CheckpointError {
+            kind: String::from(kind),
+            message: String::from(message),
+        }
+    }
+}
+
+impl From<TsplibError> for CheckpointError {
+    /// Convert a `TsplibError` raised by the shared line parsers to a `CheckpointError`
+    ///
+    /// # Arguments
+    /// * `error` - The error to convert.
+    fn from(error: TsplibError) -> Self {
+        CheckpointError {
+            kind: error.kind,
+            message: error.message,
+        }
+    }
+}
+
+impl From<std::io::Error> for CheckpointError {
+    /// Convert an `std::io::Error` to a `CheckpointError`
+    ///
+    /// # Arguments
+    /// * `error` - The error to convert.
+    fn from(error: std::io::Error) -> Self {
+        CheckpointError {
+            kind: String::from("io"),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Save the state of a colony to a file, so it can
+/// be resumed later with `load_checkpoint`.
+///
+/// # Arguments
+/// * `path` - The path of the file.
+/// * `colony` - The colony to save.
+pub fn save_checkpoint(path: &str, colony: &Colony) -> Result<(), CheckpointError> {
+    std::fs::write(path, format_checkpoint(colony))?;
+    Ok(())
+}
+
+/// Returns the state of a colony in a TSPLIB like format: the
+/// parameters and counters as `KEYWORD: value` lines followed by the
+/// cities, distances, pheromones, best tour and history sections.
+///
+/// The random number generators of the ants only depend on the seed
+/// and the iteration, so the colony continues exactly as if it was
+/// never stopped. The pheromone update is the one of `params.strategy`.
+///
+/// # Arguments
+/// * `colony` - The colony to save.
+pub fn format_checkpoint(colony: &Colony) -> String {
+    let params = &colony.params;
+    let map = &colony.map;
+    let n = map.cities.len();
+    let mut content = String::new();
+
+    // Writing to a `String` can't fail...
+    let _ = writeln!(content, "TYPE: CHECKPOINT");
+    let _ = writeln!(content, "DIMENSION: {}", n);
+    let _ = writeln!(content, "GEOMETRIC: {}", map.geometric);
+    let _ = writeln!(content, "SYMMETRIC: {}", map.is_symmetric());
+    let _ = match map.mode {
+        TourMode::Closed => writeln!(content, "MODE: CLOSED"),
+        TourMode::Open => writeln!(content, "MODE: OPEN"),
+        TourMode::Fixed { start, end } => writeln!(content, "MODE: FIXED {} {}", start, end),
+    };
+    let _ = match &map.fleet {
+        Some(fleet) => writeln!(content, "FLEET: {} {}", fleet.depot, fleet.capacity),
+        None => writeln!(content, "FLEET: NONE"),
+    };
+    let _ = writeln!(content, "LATENESS_PENALTY: {}", map.lateness_penalty);
+    let _ = writeln!(content, "SEED: {}", colony.seed());
+    let _ = writeln!(content, "ITERATION: {}", colony.iteration);
+    let _ = writeln!(content, "LAST_IMPROVEMENT: {}", colony.last_improvement);
+    let _ = writeln!(content, "BEST_LENGTH: {}", colony.best_length);
+    let _ = writeln!(content, "LOWER_BOUND: {}", optional(colony.lower_bound));
+    let _ = writeln!(content, "UPDATE_STATE: {}", join(colony.update.state().iter()));
+
+    let _ = writeln!(content, "ALPHA: {}", params.alpha);
+    let _ = writeln!(content, "BETA: {}", params.beta);
+    let _ = writeln!(content, "RHO: {}", params.rho);
+    let _ = writeln!(content, "Q: {}", params.q);
+    let _ = writeln!(content, "ANT_COUNT: {}", params.ant_count);
+    let _ = writeln!(content, "ITERATIONS: {}", params.iterations);
+    let _ = writeln!(content, "TIME_LIMIT: {}", optional(params.time_limit.map(|limit| limit.as_secs_f64())));
+    let _ = writeln!(content, "TARGET_LENGTH: {}", optional(params.target_length));
+    let _ = writeln!(content, "STAGNATION_LIMIT: {}", optional(params.stagnation_limit));
+    let _ = writeln!(content, "INITIAL_PHEROMONE: {}", params.initial_pheromone);
+    let _ = writeln!(content, "Q0: {}", params.q0);
+    let _ = writeln!(content, "CANDIDATES: {}", params.candidates);
+    let _ = writeln!(content, "THREADS: {}", params.threads);
+
+    let local_search = &params.local_search;
+    let scope = match local_search.scope {
+        LocalSearchScope::None => "NONE",
+        LocalSearchScope::EveryAnt => "EVERY_ANT",
+        LocalSearchScope::IterationBest => "ITERATION_BEST",
+    };
+
+    let _ = writeln!(content, "LOCAL_SEARCH: {}", scope);
+    let _ = writeln!(content, "TWO_OPT: {}", local_search.two_opt);
+    let _ = writeln!(content, "OR_OPT: {}", local_search.or_opt);
+    let _ = writeln!(content, "SEGMENT_INSERTION: {}", local_search.segment_insertion);
+    let _ = writeln!(content, "SEGMENT_LENGTH: {}", local_search.segment_length);
+    let _ = writeln!(content, "NEIGHBOURS: {}", local_search.neighbours);
+    let _ = writeln!(content, "INTER_ROUTE: {}", local_search.inter_route);
+
+    match &params.strategy {
+        Strategy::AntSystem => {
+            let _ = writeln!(content, "STRATEGY: ANT_SYSTEM");
+        }
+        Strategy::Elitist(elitist) => {
+            let _ = writeln!(content, "STRATEGY: ELITIST");
+            let _ = writeln!(content, "ELITIST_WEIGHT: {}", elitist.weight);
+        }
+        Strategy::RankBased(rank_based) => {
+            let _ = writeln!(content, "STRATEGY: RANK_BASED");
+            let _ = writeln!(content, "RANK_WIDTH: {}", rank_based.width);
+        }
+        Strategy::MaxMin(max_min) => {
+            let _ = writeln!(content, "STRATEGY: MAX_MIN");
+            let _ = writeln!(content, "MMAS_GLOBAL_BEST_EVERY: {}", max_min.global_best_every);
+            let _ = writeln!(content, "MMAS_P_BEST: {}", max_min.p_best);
+            let _ = writeln!(content, "MMAS_LAMBDA: {}", max_min.lambda);
+            let _ = writeln!(content, "MMAS_BRANCHING_THRESHOLD: {}", max_min.branching_threshold);
+            let _ = writeln!(content, "MMAS_STAGNATION_ITERATIONS: {}", max_min.stagnation_iterations);
+            let _ = writeln!(content, "MMAS_RESET_DELAY: {}", max_min.reset_delay);
+        }
+        Strategy::AntColonySystem(acs) => {
+            let _ = writeln!(content, "STRATEGY: ANT_COLONY_SYSTEM");
+            let _ = writeln!(content, "ACS_Q0: {}", acs.q0);
+            let _ = writeln!(content, "ACS_XI: {}", acs.xi);
+        }
+    }
+
+    let _ = writeln!(content, "CITY_SECTION");
+    for city in map.cities.iter() {
+        let _ = writeln!(content, "{} {}", city.position.x, city.position.y);
+    }
+
+    if map.fleet.is_some() {
+        let _ = writeln!(content, "DEMAND_SECTION");
+        let _ = writeln!(content, "{}", join(map.cities.iter().map(|city| city.demand)));
+    }
+
+    // One `service earliest latest` line per city, `NONE NONE` without a window.
+    if map.cities.iter().any(|city| city.time_window.is_some() || city.service_time != 0.0) {
+        let _ = writeln!(content, "TIME_WINDOW_SECTION");
+        for city in map.cities.iter() {
+            let window = city.time_window;
+            let _ = writeln!(
+                content,
+                "{} {} {}",
+                city.service_time,
+                optional(window.map(|window| window.earliest)),
+                optional(window.map(|window| window.latest)),
+            );
+        }
+    }
+
+    // Only the lower triangle of symmetric distances is saved.
+    let _ = writeln!(content, "DISTANCE_SECTION");
+    for i in 0..n {
+        let columns = if map.is_symmetric() { i + 1 } else { n };
+        let _ = writeln!(content, "{}", join((0..columns).map(|j| map.distance(i, j))));
+    }
+
+    let _ = writeln!(content, "PHEROMONE_SECTION");
+    for paths in map.pheromones.iter() {
+        let _ = writeln!(content, "{}", join(paths.iter()));
+    }
+
+    let _ = writeln!(content, "BEST_TOUR_SECTION");
+    let _ = writeln!(content, "{}", colony.best_tour.len());
+    let _ = writeln!(content, "{}", join(colony.best_tour.iter()));
+
+    let _ = writeln!(content, "HISTORY_SECTION");
+    let _ = writeln!(content, "{}", colony.history.iterations.len());
+    for stats in colony.history.iterations.iter() {
+        let _ = writeln!(
+            content,
+            "{} {} {} {} {} {} {} {}",
+            stats.iteration,
+            stats.best_length,
+            stats.mean_length,
+            stats.worst_length,
+            stats.global_best,
+            stats.branching_factor,
+            stats.reset,
+            stats.elapsed.as_secs_f64(),
+        );
+    }
+
+    content.push_str("EOF\n");
+    content
+}
+
+/// Load a colony saved by `save_checkpoint`.
+///
+/// # Arguments
+/// * `path` - The path of the file.
+pub fn load_checkpoint(path: &str) -> Result<Colony, CheckpointError> {
+    parse_checkpoint(&std::fs::read_to_string(path)?)
+}
+
+/// Parse a colony saved by `format_checkpoint`.
+///
+/// # Arguments
+/// * `content` - The content of the file.
+pub fn parse_checkpoint(content: &str) -> Result<Colony, CheckpointError> {
+    let mut params = AcoParams::default();
+    let mut local_search = LocalSearch::default();
+    let mut strategy = String::from("ANT_SYSTEM");
+    let mut elitist = ElitistParams::default();
+    let mut rank_based = RankBasedParams::default();
+    let mut max_min = MaxMinParams::default();
+    let mut acs = AcsParams::default();
+
+    let mut dimension = None;
+    let mut geometric = false;
+    let mut symmetric = true;
+    let mut mode = TourMode::Closed;
+    let mut fleet = None;
+    let mut lateness_penalty = None;
+    let mut seed = None;
+    let mut iteration = 0;
+    let mut last_improvement = 0;
+    let mut best_length = f64::INFINITY;
+    let mut lower_bound = None;
+    let mut update_state = Vec::new();
+    let mut cities = None;
+    let mut demands = None;
+    let mut time_windows = None;
+    let mut distances = None;
+    let mut pheromones = None;
+    let mut best_tour = Vec::new();
+    let mut history = History::new();
+
+    let mut lines = content.lines().enumerate();
+
+    while let Some((number, line)) = lines.next() {
+        let (keyword, value) = split_keyword(line);
+
+        match keyword {
+            "" => continue,
+            "TYPE" => {
+                if value != "CHECKPOINT" {
+                    return Err(CheckpointError::new("format", &format!("unsupported TYPE `{}`", value)));
+                }
+            }
+            "DIMENSION" => dimension = Some(parse_number(number, value)?),
+            "GEOMETRIC" => geometric = parse_number(number, value)?,
+            "SYMMETRIC" => symmetric = parse_number(number, value)?,
+            "MODE" => {
+                let words: Vec<&str> = value.split_whitespace().collect();
+
+                mode = match words.as_slice() {
+                    ["CLOSED"] => TourMode::Closed,
+                    ["OPEN"] => TourMode::Open,
+                    ["FIXED", start, end] => TourMode::Fixed {
+                        start: parse_number(number, start)?,
+                        end: parse_number(number, end)?,
+                    },
+                    _ => return Err(invalid(number, value)),
+                };
+            }
+            "FLEET" => {
+                let words: Vec<&str> = value.split_whitespace().collect();
+
+                fleet = match words.as_slice() {
+                    ["NONE"] => None,
+                    [depot, capacity] => Some(Fleet::new(parse_number(number, depot)?, parse_number(number, capacity)?)),
+                    _ => return Err(invalid(number, value)),
+                };
+            }
+            "LATENESS_PENALTY" => lateness_penalty = Some(parse_number(number, value)?),
+            "SEED" => seed = Some(parse_number(number, value)?),
+            "ITERATION" => iteration = parse_number(number, value)?,
+            "LAST_IMPROVEMENT" => last_improvement = parse_number(number, value)?,
+            "BEST_LENGTH" => best_length = parse_number(number, value)?,
+            "LOWER_BOUND" => lower_bound = parse_optional(number, value)?,
+            "UPDATE_STATE" => {
+                update_state = value
+                    .split_whitespace()
+                    .map(|value| parse_number(number, value))
+                    .collect::<Result<_, _>>()?;
+            }
+            "ALPHA" => params.alpha = parse_number(number, value)?,
+            "BETA" => params.beta = parse_number(number, value)?,
+            "RHO" => params.rho = parse_number(number, value)?,
+            "Q" => params.q = parse_number(number, value)?,
+            "ANT_COUNT" => params.ant_count = parse_number(number, value)?,
+            "ITERATIONS" => params.iterations = parse_number(number, value)?,
+            "TIME_LIMIT" => {
+                params.time_limit = match parse_optional::<f64>(number, value)? {
+                    Some(seconds) => Some(Duration::try_from_secs_f64(seconds).map_err(|_| invalid(number, value))?),
+                    None => None,
+                };
+            }
+            "TARGET_LENGTH" => params.target_length = parse_optional(number, value)?,
+            "STAGNATION_LIMIT" => params.stagnation_limit = parse_optional(number, value)?,
+            "INITIAL_PHEROMONE" => params.initial_pheromone = parse_number(number, value)?,
+            "Q0" => params.q0 = parse_number(number, value)?,
+            "CANDIDATES" => params.candidates = parse_number(number, value)?,
+            "THREADS" => params.threads = parse_number(number, value)?,
+            "LOCAL_SEARCH" => {
+                local_search.scope = match value {
+                    "NONE" => LocalSearchScope::None,
+                    "EVERY_ANT" => LocalSearchScope::EveryAnt,
+                    "ITERATION_BEST" => LocalSearchScope::IterationBest,
+                    _ => return Err(invalid(number, value)),
+                };
+            }
+            "TWO_OPT" => local_search.two_opt = parse_number(number, value)?,
+            "OR_OPT" => local_search.or_opt = parse_number(number, value)?,
+            "SEGMENT_INSERTION" => local_search.segment_insertion = parse_number(number, value)?,
+            "SEGMENT_LENGTH" => local_search.segment_length = parse_number(number, value)?,
+            "NEIGHBOURS" => local_search.neighbours = parse_number(number, value)?,
+            "INTER_ROUTE" => local_search.inter_route = parse_number(number, value)?,
+            "STRATEGY" => strategy = String::from(value),
+            "ELITIST_WEIGHT" => elitist.weight = parse_number(number, value)?,
+            "RANK_WIDTH" => rank_based.width = parse_number(number, value)?,
+            "MMAS_GLOBAL_BEST_EVERY" => max_min.global_best_every = parse_number(number, value)?,
+            "MMAS_P_BEST" => max_min.p_best = parse_number(number, value)?,
+            "MMAS_LAMBDA" => max_min.lambda = parse_number(number, value)?,
+            "MMAS_BRANCHING_THRESHOLD" => max_min.branching_threshold = parse_number(number, value)?,
+            "MMAS_STAGNATION_ITERATIONS" => max_min.stagnation_iterations = parse_number(number, value)?,
+            "MMAS_RESET_DELAY" => max_min.reset_delay = parse_number(number, value)?,
+            "ACS_Q0" => acs.q0 = parse_number(number, value)?,
+            "ACS_XI" => acs.xi = parse_number(number, value)?,
+            "CITY_SECTION" => {
+                let dimension = require_dimension(dimension)?;
+                let numbers = read_numbers::<f32, _>(&mut lines, dimension * 2, keyword)?;
+                cities = Some(
+                    numbers
+                        .chunks(2)
+                        .map(|position| City::new(Vector2::new(position[0], position[1])))
+                        .collect::<Vec<City>>(),
+                );
+            }
+            "DEMAND_SECTION" => {
+                let dimension = require_dimension(dimension)?;
+                demands = Some(read_numbers::<f64, _>(&mut lines, dimension, keyword)?);
+            }
+            "TIME_WINDOW_SECTION" => {
+                let dimension = require_dimension(dimension)?;
+                let mut windows = Vec::with_capacity(dimension);
+
+                for _ in 0..dimension {
+                    let (number, line) = lines.next().ok_or_else(|| {
+                        CheckpointError::new("format", &format!("{} ends before {} cities", keyword, dimension))
+                    })?;
+
+                    windows.push(parse_time_window(number, line)?);
+                }
+
+                time_windows = Some(windows);
+            }
+            "DISTANCE_SECTION" => {
+                let dimension = require_dimension(dimension)?;
+                let count = if symmetric { dimension * (dimension + 1) / 2 } else { dimension * dimension };
+                distances = Some(read_numbers::<f64, _>(&mut lines, count, keyword)?);
+            }
+            "PHEROMONE_SECTION" => {
+                let dimension = require_dimension(dimension)?;
+                let numbers = read_numbers::<f64, _>(&mut lines, dimension * dimension, keyword)?;
+                pheromones = Some(numbers.chunks(dimension).map(|paths| paths.to_vec()).collect::<Vec<_>>());
+            }
+            "BEST_TOUR_SECTION" => {
+                let size = read_numbers::<usize, _>(&mut lines, 1, keyword)?[0];
+                best_tour = read_numbers(&mut lines, size, keyword)?;
+            }
+            "HISTORY_SECTION" => {
+                let size = read_numbers::<usize, _>(&mut lines, 1, keyword)?[0];
+
+                for _ in 0..size {
+                    let (number, line) = lines.next().ok_or_else(|| {
+                        CheckpointError::new("format", &format!("{} ends before {} iterations", keyword, size))
+                    })?;
+
+                    history.iterations.push(parse_stats(number, line)?);
+                }
+            }
+            "EOF" => break,
+            _ => return Err(invalid(number, keyword)),
+        }
+    }
+
+    let dimension = require_dimension(dimension)?;
+    let mut cities = cities.ok_or_else(|| CheckpointError::new("format", "CITY_SECTION is missing"))?;
+    let distances = distances.ok_or_else(|| CheckpointError::new("format", "DISTANCE_SECTION is missing"))?;
+    let pheromones = pheromones.ok_or_else(|| CheckpointError::new("format", "PHEROMONE_SECTION is missing"))?;
+    let seed = seed.ok_or_else(|| CheckpointError::new("format", "SEED is missing"))?;
+
+    if best_tour.iter().any(|&city| city >= dimension) {
+        return Err(CheckpointError::new("format", "the best tour contains an unknown city"));
+    }
+
+    params.seed = Some(seed);
+    params.local_search = local_search;
+    params.strategy = match strategy.as_str() {
+        "ANT_SYSTEM" => Strategy::AntSystem,
+        "ELITIST" => Strategy::Elitist(elitist),
+        "RANK_BASED" => Strategy::RankBased(rank_based),
+        "MAX_MIN" => Strategy::MaxMin(max_min),
+        "ANT_COLONY_SYSTEM" => Strategy::AntColonySystem(acs),
+        _ => return Err(CheckpointError::new("format", &format!("unknown STRATEGY `{}`", strategy))),
+    };
+
+    // Row `i` of the lower triangle starts at `i * (i + 1) / 2`.
+    let distances = if symmetric {
+        SquareMatrix::symmetric(dimension, |i, j| {
+            let (i, j) = if i >= j { (i, j) } else { (j, i) };
+            distances[i * (i + 1) / 2 + j]
+        })
+    } else {
+        SquareMatrix::full(dimension, |i, j| distances[i * dimension + j])
+    };
+
+    if let Some(demands) = demands {
+        for (city, demand) in cities.iter_mut().zip(demands) {
+            city.demand = demand;
+        }
+    }
+
+    if let Some(time_windows) = time_windows {
+        for (city, (service_time, time_window)) in cities.iter_mut().zip(time_windows) {
+            city.service_time = service_time;
+            city.time_window = time_window;
+        }
+    }
+
+    let mut map = Map::with_distances(&cities, distances);
+    map.geometric = geometric;
+    map.mode = mode;
+    map.fleet = fleet;
+
+    if let Some(lateness_penalty) = lateness_penalty {
+        map.lateness_penalty = lateness_penalty;
+    }
+
+    let mut colony = Colony::with_seed(map, params, seed)
+        .map_err(|error| CheckpointError::new("params", &format!("{}: {}", error.kind, error.message)))?;
+
+    colony.map.pheromones = pheromones;
+    colony.update.restore(&update_state);
+    colony.best_tour = best_tour;
+    colony.best_length = best_length;
+    colony.lower_bound = lower_bound;
+    colony.iteration = iteration;
+    colony.last_improvement = last_improvement;
+    colony.history = history;
+
+    Ok(colony)
+}
+
+/// Returns the values separated by a space.
+fn join<T: std::fmt::Display, I: Iterator<Item = T>>(values: I) -> String {
+    values.map(|value| value.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+/// Returns the value, or `NONE`.
+fn optional<T: std::fmt::Display>(value: Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => String::from("NONE"),
+    }
+}
+
+fn parse_optional<T: FromStr>(number: usize, value: &str) -> Result<Option<T>, CheckpointError> {
+    match value {
+        "NONE" => Ok(None),
+        _ => Ok(Some(parse_number(number, value)?)),
+    }
+}
+
+fn invalid(number: usize, value: &str) -> CheckpointError {
+    CheckpointError::new("format", &format!("line {}: invalid value `{}`", number + 1, value))
+}
+
+fn require_dimension(dimension: Option<usize>) -> Result<usize, CheckpointError> {
+    dimension.ok_or_else(|| CheckpointError::new("format", "DIMENSION must be given before the sections"))
+}
+
+/// Parse the statistics of an iteration, written on one line.
+fn parse_stats(number: usize, line: &str) -> Result<IterationStats, CheckpointError> {
+    let values: Vec<&str> = line.split_whitespace().collect();
+
+    if values.len() != 8 {
+        return Err(CheckpointError::new(
+            "format",
+            &format!("line {}: 8 values expected, {} found", number + 1, values.len()),
+        ));
+    }
+
+    Ok(IterationStats {
+        iteration: parse_number(number, values[0])?,
+        best_length: parse_number(number, values[1])?,
+        mean_length: parse_number(number, values[2])?,
+        worst_length: parse_number(number, values[3])?,
+        global_best: parse_number(number, values[4])?,
+        branching_factor: parse_number(number, values[5])?,
+        reset: parse_number(number, values[6])?,
+        elapsed: Duration::try_from_secs_f64(parse_number(number, values[7])?)
+            .map_err(|_| invalid(number, values[7]))?,
+    })
+}
+
+/// Parse the service time and the time window of a city, written on one line.
+fn parse_time_window(number: usize, line: &str) -> Result<(f64, Option<TimeWindow>), CheckpointError> {
+    let values: Vec<&str> = line.split_whitespace().collect();
+
+    if values.len() != 3 {
+        return Err(CheckpointError::new(
+            "format",
+            &format!("line {}: 3 values expected, {} found", number + 1, values.len()),
+        ));
+    }
+
+    let earliest = parse_optional(number, values[1])?;
+    let latest = parse_optional(number, values[2])?;
+
+    let time_window = match (earliest, latest) {
+        (Some(earliest), Some(latest)) => Some(TimeWindow::new(earliest, latest)),
+        (None, None) => None,
+        _ => return Err(invalid(number, line)),
+    };
+
+    Ok((parse_number(number, values[0])?, time_window))
+}
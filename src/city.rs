@@ -1,18 +1,43 @@
-
 use cgmath::Vector2;
 
+use crate::schedule::TimeWindow;
+
 pub struct City {
     pub position: Vector2<f32>,
+
+    /// What must be delivered to the city in a vehicle
+    /// routing problem (see `vrp::Fleet`), `0` otherwise.
+    pub demand: f64,
+
+    /// How long a vehicle stays in the city.
+    pub service_time: f64,
+
+    /// When the service of the city may start, `None` at any time.
+    pub time_window: Option<TimeWindow>,
 }
 
 impl City {
     pub fn new(position: Vector2::<f32>) -> City {
-        City { position: position }
+        City { position, demand: 0.0, service_time: 0.0, time_window: None }
+    }
+
+    /// Create new city that must be delivered.
+    ///
+    /// # Arguments
+    /// * `position` - The position of the city.
+    /// * `demand` - What must be delivered to the city.
+    pub fn with_demand(position: Vector2<f32>, demand: f64) -> City {
+        City { position, demand, service_time: 0.0, time_window: None }
     }
 }
 
 impl std::clone::Clone for City {
     fn clone(&self) -> Self {
-        City { position: self.position }
+        City {
+            position: self.position,
+            demand: self.demand,
+            service_time: self.service_time,
+            time_window: self.time_window,
+        }
     }
-}
\ No newline at end of file
+}
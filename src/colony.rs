@@ -0,0 +1,512 @@
+use crate::ant::Ant;
+use crate::bounds;
+use crate::city::City;
+use crate::local_search::LocalSearchScope;
+use crate::map::{Map, TourMode};
+use crate::observer::{Control, Observer};
+use crate::params::{AcoParams, ParamsError};
+use crate::schedule::{self, Schedule};
+use crate::stats::{History, IterationStats, BRANCHING_LAMBDA};
+use crate::strategy::{Iteration, PheromoneUpdate};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use std::time::{Duration, Instant};
+
+/// Why a colony stopped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Termination {
+    /// An observer asked the colony to stop.
+    Cancelled,
+
+    /// A tour at least as short as `params.target_length` was found.
+    TargetLength,
+
+    /// `params.iterations` iterations were done.
+    Iterations,
+
+    /// `params.time_limit` was spent.
+    TimeLimit,
+
+    /// The best tour wasn't improved for `params.stagnation_limit` iterations.
+    Stagnation,
+}
+
+/// The result of a colony run.
+#[derive(Clone, Debug)]
+pub struct ColonyResult {
+    /// The shortest tour walked by an ant.
+    pub best_tour: Vec<usize>,
+
+    /// The length of the shortest tour. When some cities have a time
+    /// window, the lateness penalty is included (see `Map::tour_cost`).
+    pub best_length: f64,
+
+    /// When each city of the best tour is served, `None`
+    /// unless some cities have a time window.
+    pub schedule: Option<Schedule>,
+
+    /// The number of iterations done.
+    pub iterations: usize,
+
+    /// The statistics of every iteration.
+    pub history: History,
+
+    /// Why the colony stopped, `None` if it can still iterate.
+    pub termination: Option<Termination>,
+
+    /// A lower bound of the length of the tours, if one was given
+    /// to the colony (see `Colony::lower_bound`).
+    pub lower_bound: Option<f64>,
+
+    /// The percentage by which the best tour exceeds `lower_bound`.
+    pub gap: Option<f64>,
+}
+
+/// A colony of ants that explore a map.
+pub struct Colony {
+    /// The map explored by the ants.
+    pub map: Map,
+
+    /// The colony parameters.
+    pub params: AcoParams,
+
+    /// The ants of the colony.
+    pub ants: Vec<Ant>,
+
+    /// The shortest tour walked by an ant so far.
+    pub best_tour: Vec<usize>,
+
+    /// The length of the shortest tour so far, lateness
+    /// penalty included (see `Map::tour_cost`).
+    pub best_length: f64,
+
+    /// The number of iterations done so far.
+    pub iteration: usize,
+
+    /// The statistics of the iterations done so far.
+    pub history: History,
+
+    /// A lower bound of the length of the tours, used to report how far
+    /// the best tour may be from the optimal one. It is not computed by
+    /// the colony, set it with one of the functions of `bounds`.
+    pub lower_bound: Option<f64>,
+
+    /// The seed of the random number generators of the ants.
+    seed: u64,
+
+    /// The nearest neighbours of each city, used by the local search.
+    neighbours: Vec<Vec<usize>>,
+
+    /// The rule used to update the pheromones.
+    pub(crate) update: Box<dyn PheromoneUpdate>,
+
+    /// The iteration where the best tour was last improved.
+    pub(crate) last_improvement: usize,
+
+    /// The observers notified of the progress of the colony.
+    observers: Vec<Box<dyn Observer>>,
+
+    /// `true` once an observer asked the colony to stop.
+    cancelled: bool,
+
+    /// When the first iteration of this run started, and the time
+    /// already spent before it (by a colony resumed from a checkpoint).
+    clock: Option<(Instant, Duration)>,
+}
+
+impl Colony {
+    /// Create new colony. The pheromones of the map are set
+    /// by the pheromone update of `params.strategy` and its
+    /// heuristic values are computed with `params.beta`. The candidate
+    /// lists are built when `params.candidates` is not `0`.
+    ///
+    /// Each ant draws the cities with its own random number generator,
+    /// seeded from `params.seed`, the iteration and the index of the ant.
+    ///
+    /// # Arguments
+    /// * `map` - The map to explore.
+    /// * `params` - The colony parameters.
+    pub fn new(map: Map, params: AcoParams) -> Result<Colony, ParamsError> {
+        let seed = match params.seed {
+            Some(seed) => seed,
+            None => rand::random(),
+        };
+
+        Colony::with_seed(map, params, seed)
+    }
+
+    /// Create new colony whose seed is drawn from the
+    /// given random number generator (`params.seed` is ignored).
+    ///
+    /// # Arguments
+    /// * `map` - The map to explore.
+    /// * `params` - The colony parameters.
+    /// * `rng` - The random number generator that gives the seed.
+    pub fn with_rng(map: Map, params: AcoParams, mut rng: StdRng) -> Result<Colony, ParamsError> {
+        let seed = rng.gen();
+        Colony::with_seed(map, params, seed)
+    }
+
+    /// Create new colony with the given seed (`params.seed` is ignored).
+    ///
+    /// # Arguments
+    /// * `map` - The map to explore.
+    /// * `params` - The colony parameters.
+    /// * `seed` - The seed of the random number generators of the ants.
+    pub fn with_seed(mut map: Map, params: AcoParams, seed: u64) -> Result<Colony, ParamsError> {
+        if map.cities.is_empty() {
+            return Err(ParamsError::new("map", "the map must contain at least one city"));
+        }
+
+        params.validate()?;
+
+        if let TourMode::Fixed { start, end } = map.mode {
+            if start == end || start.max(end) >= map.cities.len() {
+                return Err(ParamsError::new("map", "a fixed path must join two different cities of the map"));
+            }
+        }
+
+        if let Some(fleet) = &map.fleet {
+            if fleet.depot >= map.cities.len() || map.mode != TourMode::Closed {
+                return Err(ParamsError::new("map", "the depot must be a city of the map and the tours closed"));
+            }
+
+            if !map.cities.iter().all(|city| (0.0..=fleet.capacity).contains(&city.demand)) {
+                return Err(ParamsError::new("map", "each demand must be positive and fit in a vehicle"));
+            }
+        }
+
+        let invalid_window = |city: &City| city.time_window.is_some_and(|window| window.earliest > window.latest);
+
+        if map.cities.iter().any(|city| city.service_time < 0.0 || invalid_window(city)) || map.lateness_penalty < 0.0 {
+            return Err(ParamsError::new("map", "the time windows, service times and lateness penalty must be valid"));
+        }
+
+        map.set_beta(params.beta);
+        map.build_candidates(params.candidates);
+
+        // Spread the ants over the cities...
+        let ants = (0..params.ant_count)
+            .map(|i| Ant::new(i % map.cities.len()))
+            .collect();
+
+        let neighbours = match params.local_search.scope {
+            LocalSearchScope::None => Vec::new(),
+            _ => map.nearest_neighbours(params.local_search.neighbours),
+        };
+
+        let mut update = params.strategy.build();
+        update.initialize(&mut map, &params);
+
+        Ok(Colony {
+            map,
+            params,
+            ants,
+            best_tour: Vec::new(),
+            best_length: f64::INFINITY,
+            iteration: 0,
+            history: History::new(),
+            lower_bound: None,
+            seed,
+            neighbours,
+            update,
+            last_improvement: 0,
+            observers: Vec::new(),
+            cancelled: false,
+            clock: None,
+        })
+    }
+
+    /// Returns the seed of the random number generators of the ants.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Add an observer that is notified of the progress of the colony.
+    ///
+    /// # Arguments
+    /// * `observer` - The observer.
+    pub fn add_observer(&mut self, observer: Box<dyn Observer>) {
+        self.observers.push(observer);
+    }
+
+    /// Call `f` with each observer. The colony is cancelled
+    /// if one of them returns `Control::Stop`.
+    fn notify<F: FnMut(&mut dyn Observer, &Colony) -> Control>(&mut self, mut f: F) {
+        // The observers are taken out so they can look at the colony...
+        let mut observers = std::mem::take(&mut self.observers);
+
+        for observer in observers.iter_mut() {
+            if f(observer.as_mut(), self) == Control::Stop {
+                self.cancelled = true;
+            }
+        }
+
+        self.observers = observers;
+    }
+
+    /// Replace the pheromone update of `params.strategy` by another
+    /// one, which sets the pheromones of the map again.
+    ///
+    /// # Arguments
+    /// * `update` - The rule used to update the pheromones.
+    pub fn set_pheromone_update(&mut self, update: Box<dyn PheromoneUpdate>) {
+        self.update = update;
+        self.update.initialize(&mut self.map, &self.params);
+    }
+
+    /// Let every ant walk a tour, then update
+    /// the pheromones, the best tour and the history.
+    ///
+    /// The ants only read the pheromones while they walk, so they are
+    /// spread over `params.threads` threads. When the pheromone update
+    /// has a local update, the ants walk one after the other instead
+    /// and the local update is applied as soon as an ant's tour is done.
+    ///
+    /// The observers are notified along the way. Nothing is done if
+    /// one of them asks to stop before the iteration starts.
+    pub fn iterate(&mut self) {
+        self.start_clock();
+        self.notify(|observer, colony| observer.iteration_started(colony));
+
+        if self.cancelled {
+            return
+        }
+
+        let (seed, iteration) = (self.seed, self.iteration);
+
+        if self.update.updates_locally() {
+            for (i, ant) in self.ants.iter_mut().enumerate() {
+                ant.explore_map(&self.map, &self.params, &mut ant_rng(seed, iteration, i));
+                self.update.local_update(&mut self.map, ant, &self.params);
+            }
+        } else {
+            let (map, params) = (&self.map, &self.params);
+
+            for_each_ant(&mut self.ants, params.thread_count(), |i, ant| {
+                ant.explore_map(map, params, &mut ant_rng(seed, iteration, i));
+            });
+        }
+
+        self.improve_tours();
+
+        let iteration_best = self.iteration_best();
+        let ant = &self.ants[iteration_best];
+
+        if ant.length < self.best_length {
+            self.best_length = ant.length;
+            self.best_tour = ant.tour.clone();
+            self.last_improvement = self.iteration;
+            self.notify(|observer, colony| observer.new_best(colony));
+        }
+
+        let iteration = Iteration {
+            ants: &self.ants,
+            iteration_best,
+            best_tour: &self.best_tour,
+            best_length: self.best_length,
+            iteration: self.iteration,
+            last_improvement: self.last_improvement,
+        };
+
+        let reset = self.update.update(&mut self.map, &iteration, &self.params);
+
+        if reset {
+            self.notify(|observer, colony| observer.pheromones_reset(colony));
+        }
+
+        self.record(iteration_best, reset);
+        self.iteration += 1;
+
+        self.notify(|observer, colony| {
+            let stats = colony.history.iterations.last().unwrap();
+            observer.iteration_ended(colony, stats)
+        });
+    }
+
+    /// Add the statistics of the current iteration to the history.
+    ///
+    /// # Arguments
+    /// * `iteration_best` - The index of the ant that walked the shortest tour of the iteration.
+    /// * `reset` - `true` if the pheromones were reset.
+    fn record(&mut self, iteration_best: usize, reset: bool) {
+        let lengths = self.ants.iter().map(|ant| ant.length);
+        let worst_length = lengths.clone().fold(f64::NEG_INFINITY, f64::max);
+        let mean_length = lengths.sum::<f64>() / self.ants.len() as f64;
+        let elapsed = self.elapsed();
+
+        self.history.iterations.push(IterationStats {
+            iteration: self.iteration,
+            best_length: self.ants[iteration_best].length,
+            mean_length,
+            worst_length,
+            global_best: self.best_length,
+            branching_factor: self.map.branching_factor(BRANCHING_LAMBDA),
+            reset,
+            elapsed,
+        });
+    }
+
+    /// Returns the index of the ant that walked
+    /// the shortest tour of the iteration.
+    fn iteration_best(&self) -> usize {
+        let mut best = 0;
+
+        for (i, ant) in self.ants.iter().enumerate() {
+            if ant.length < self.ants[best].length {
+                best = i;
+            }
+        }
+
+        best
+    }
+
+    /// Improve the tours of the ants with the local search.
+    fn improve_tours(&mut self) {
+        let local_search = &self.params.local_search;
+
+        let range = match local_search.scope {
+            LocalSearchScope::None => return,
+            LocalSearchScope::EveryAnt => 0..self.ants.len(),
+            LocalSearchScope::IterationBest => {
+                let best = self.iteration_best();
+                best..best + 1
+            }
+        };
+
+        let (map, neighbours) = (&self.map, &self.neighbours);
+
+        for_each_ant(&mut self.ants[range], self.params.thread_count(), |_, ant| {
+            if local_search.improve(map, neighbours, &mut ant.tour) {
+                ant.length = map.tour_cost(&ant.tour);
+            }
+        });
+    }
+
+    /// Returns the criterion that stops the colony, if any. When
+    /// several are met, the first one of the `Termination` variants
+    /// is returned.
+    pub fn termination(&self) -> Option<Termination> {
+        let params = &self.params;
+
+        if self.cancelled {
+            return Some(Termination::Cancelled)
+        }
+
+        if params.target_length.is_some_and(|target| self.best_length <= target) {
+            return Some(Termination::TargetLength)
+        }
+
+        if self.iteration >= params.iterations {
+            return Some(Termination::Iterations)
+        }
+
+        if params.time_limit.is_some_and(|limit| self.elapsed() >= limit) {
+            return Some(Termination::TimeLimit)
+        }
+
+        // `last_improvement` is the last iteration where the best tour was improved...
+        let stagnating = |limit: usize| self.iteration > self.last_improvement + limit;
+
+        if params.stagnation_limit.is_some_and(stagnating) {
+            return Some(Termination::Stagnation)
+        }
+
+        None
+    }
+
+    /// Returns the wall-clock time spent since the first iteration
+    /// started, observers included. The time spent before a colony
+    /// was saved to a checkpoint is counted too.
+    pub fn elapsed(&self) -> Duration {
+        match self.clock {
+            Some((start, before)) => before + start.elapsed(),
+            None => self.history.iterations.last().map_or(Duration::ZERO, |stats| stats.elapsed),
+        }
+    }
+
+    /// Start measuring the time when the first iteration starts.
+    fn start_clock(&mut self) {
+        if self.clock.is_none() {
+            self.clock = Some((Instant::now(), self.elapsed()));
+        }
+    }
+
+    /// Run until one of the termination criteria of
+    /// the parameters is met and returns the best tour found.
+    pub fn run(&mut self) -> ColonyResult {
+        while self.termination().is_none() {
+            self.iterate();
+        }
+
+        self.result()
+    }
+
+    /// Returns the best tour found so far and the history.
+    pub fn result(&self) -> ColonyResult {
+        ColonyResult {
+            best_tour: self.best_tour.clone(),
+            best_length: self.best_length,
+            schedule: if self.map.has_time_windows() && !self.best_tour.is_empty() {
+                Some(schedule::schedule(&self.map, &self.best_tour))
+            } else {
+                None
+            },
+            iterations: self.iteration,
+            history: self.history.clone(),
+            termination: self.termination(),
+            lower_bound: self.lower_bound,
+            gap: match self.lower_bound {
+                Some(bound) if !self.best_tour.is_empty() => Some(bounds::gap(self.map.tour_length(&self.best_tour), bound)),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Call `f` with the index of each ant and the ant,
+/// spreading the ants over `threads` threads.
+fn for_each_ant<F: Fn(usize, &mut Ant) + Sync>(ants: &mut [Ant], threads: usize, f: F) {
+    if threads <= 1 || ants.len() <= 1 {
+        for (i, ant) in ants.iter_mut().enumerate() {
+            f(i, ant);
+        }
+
+        return
+    }
+
+    let chunk = ants.len().div_ceil(threads);
+    let f = &f;
+
+    std::thread::scope(|scope| {
+        for (c, ants) in ants.chunks_mut(chunk).enumerate() {
+            scope.spawn(move || {
+                for (i, ant) in ants.iter_mut().enumerate() {
+                    f(c * chunk + i, ant);
+                }
+            });
+        }
+    });
+}
+
+/// Returns the random number generator of an ant for an iteration,
+/// so the tours don't depend on the order the ants walk them.
+///
+/// # Arguments
+/// * `seed` - The seed of the colony.
+/// * `iteration` - The iteration.
+/// * `ant` - The index of the ant.
+fn ant_rng(seed: u64, iteration: usize, ant: usize) -> StdRng {
+    let seed = splitmix(splitmix(splitmix(seed) ^ iteration as u64) ^ ant as u64);
+    StdRng::seed_from_u64(seed)
+}
+
+/// Mix the bits of a value (the SplitMix64 finalizer).
+fn splitmix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
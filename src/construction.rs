@@ -0,0 +1,406 @@
+use crate::map::Map;
+
+use std::cmp::Ordering;
+
+/// How many nearest neighbours of each city give
+/// the edges looked at by `greedy_edge`.
+const GREEDY_NEIGHBOURS: usize = 10;
+
+/// Returns a tour that starts at `start` and always goes to the
+/// nearest unvisited city. The candidate lists of the map are
+/// looked at first when the map has some. On a vehicle routing
+/// problem, the giant tour starts at the depot instead and goes
+/// back to it when the nearest city doesn't fit in the vehicle.
+///
+/// # Arguments
+/// * `map` - A map.
+/// * `start` - The index of the first city.
+pub fn nearest_neighbour(map: &Map, start: usize) -> Vec<usize> {
+    let n = map.cities.len();
+    let mut visited = vec![false; n];
+    let mut tour = Vec::with_capacity(n);
+
+    if n == 0 {
+        return tour
+    }
+
+    let start = map.fleet.map_or(start, |fleet| fleet.depot);
+    let mut remaining = n - 1;
+    let mut load = 0.0;
+
+    tour.push(start);
+    visited[start] = true;
+
+    while remaining > 0 {
+        let current = tour[tour.len() - 1];
+        let next = map
+            .candidates(current)
+            .iter()
+            .copied()
+            .find(|&i| !visited[i])
+            .unwrap_or_else(|| nearest(map, current, (0..n).filter(|&i| !visited[i])).unwrap());
+
+        if let Some(fleet) = &map.fleet {
+            if current != fleet.depot && load + map.cities[next].demand > fleet.capacity {
+                tour.push(fleet.depot);
+                load = 0.0;
+                continue
+            }
+
+            load += map.cities[next].demand;
+        }
+
+        visited[next] = true;
+        tour.push(next);
+        remaining -= 1;
+    }
+
+    tour
+}
+
+/// Returns the initial pheromones `1 / (n * L_nn)`, where `L_nn` is the
+/// length of the nearest neighbour tour that starts at the first city.
+///
+/// # Arguments
+/// * `map` - A map.
+pub fn nearest_neighbour_pheromone(map: &Map) -> f64 {
+    let length = map.tour_length(&nearest_neighbour(map, 0));
+
+    if length > 0.0 {
+        1.0 / (map.cities.len() as f64 * length)
+    } else {
+        1.0
+    }
+}
+
+/// Returns a tour built by adding the shortest edges first, as
+/// long as no city gets more than two edges and no cycle is closed.
+/// Only the edges to the nearest neighbours of each city are tried,
+/// the remaining fragments are then joined by their nearest ends.
+///
+/// # Arguments
+/// * `map` - A map.
+pub fn greedy_edge(map: &Map) -> Vec<usize> {
+    let n = map.cities.len();
+
+    if n <= 3 {
+        return (0..n).collect()
+    }
+
+    let mut edges = Vec::new();
+
+    for (i, neighbours) in map.nearest_neighbours(GREEDY_NEIGHBOURS).iter().enumerate() {
+        for &j in neighbours.iter() {
+            edges.push((map.distance(i, j), i.min(j), i.max(j)));
+        }
+    }
+
+    // The edges found from both of their cities are now next to each other...
+    edges.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    edges.dedup();
+
+    // The (up to two) cities linked to each city, and the fragment of each city.
+    let mut links = vec![Vec::with_capacity(2); n];
+    let mut fragments = Fragments::new(n);
+    let mut count = 0;
+
+    for &(_, i, j) in edges.iter() {
+        if links[i].len() < 2 && links[j].len() < 2 && fragments.join(i, j) {
+            links[i].push(j);
+            links[j].push(i);
+            count += 1;
+
+            if count == n - 1 {
+                break
+            }
+        }
+    }
+
+    // Join the fragments, going from the end of one to the nearest end of another.
+    while count < n - 1 {
+        let current = (0..n).find(|&i| links[i].len() < 2).unwrap();
+        let end = fragment_end(&links, current);
+        let ends = (0..n).filter(|&i| links[i].len() < 2 && !fragments.same(i, end));
+
+        let next = nearest(map, end, ends).unwrap();
+        fragments.join(end, next);
+        links[end].push(next);
+        links[next].push(end);
+        count += 1;
+    }
+
+    // Close the path...
+    let ends: Vec<usize> = (0..n).filter(|&i| links[i].len() < 2).collect();
+    links[ends[0]].push(ends[1]);
+    links[ends[1]].push(ends[0]);
+
+    walk(&links, 0)
+}
+
+/// Returns a tour built by inserting, at each step, the city that
+/// lengthens the tour the least, where it lengthens it the least.
+///
+/// # Arguments
+/// * `map` - A map.
+pub fn cheapest_insertion(map: &Map) -> Vec<usize> {
+    let n = map.cities.len();
+
+    if n <= 3 {
+        return (0..n).collect()
+    }
+
+    // The tour is a linked list, `next[i]` follows city `i`.
+    let first = nearest(map, 0, 1..n).unwrap();
+    let mut next = vec![usize::MAX; n];
+    next[0] = first;
+    next[first] = 0;
+
+    // For each city outside the tour, the cheapest insertion (cost, city after which it goes).
+    let mut best: Vec<(f64, usize)> = (0..n).map(|c| cheapest_edge(map, &next, 0, c)).collect();
+
+    for _ in 2..n {
+        let c = (0..n)
+            .filter(|&c| next[c] == usize::MAX)
+            .min_by(|&a, &b| best[a].0.partial_cmp(&best[b].0).unwrap_or(Ordering::Equal))
+            .unwrap();
+
+        let a = best[c].1;
+        let b = next[a];
+        next[a] = c;
+        next[c] = b;
+
+        for u in (0..n).filter(|&u| next[u] == usize::MAX) {
+            if best[u].1 == a {
+                // The edge (a, b) is gone, look at the whole tour again.
+                best[u] = cheapest_edge(map, &next, c, u);
+            } else {
+                for &(from, to) in [(a, c), (c, b)].iter() {
+                    let cost = insertion_cost(map, from, to, u);
+
+                    if cost < best[u].0 {
+                        best[u] = (cost, from);
+                    }
+                }
+            }
+        }
+    }
+
+    walk_next(&next, 0)
+}
+
+/// Returns a tour built by inserting, at each step, the city that is
+/// the farthest from the tour, where it lengthens the tour the least.
+///
+/// # Arguments
+/// * `map` - A map.
+pub fn farthest_insertion(map: &Map) -> Vec<usize> {
+    let n = map.cities.len();
+
+    if n <= 3 {
+        return (0..n).collect()
+    }
+
+    let mut next = vec![usize::MAX; n];
+    next[0] = 0;
+
+    // The distance from each city to the nearest city of the tour.
+    let mut distance: Vec<f64> = (0..n).map(|c| map.distance(0, c)).collect();
+
+    for _ in 1..n {
+        let c = (0..n)
+            .filter(|&c| next[c] == usize::MAX)
+            .max_by(|&a, &b| distance[a].partial_cmp(&distance[b]).unwrap_or(Ordering::Equal))
+            .unwrap();
+
+        let (_, a) = cheapest_edge(map, &next, 0, c);
+        let b = next[a];
+        next[a] = c;
+        next[c] = b;
+
+        for u in (0..n).filter(|&u| next[u] == usize::MAX) {
+            distance[u] = distance[u].min(map.distance(c, u));
+        }
+    }
+
+    walk_next(&next, 0)
+}
+
+/// Returns a tour that visits the cities in the order of a depth-first
+/// walk of a minimum spanning tree (the double-tree algorithm). It is
+/// at most twice as long as the optimal tour when the distances respect
+/// the triangle inequality.
+///
+/// # Arguments
+/// * `map` - A map.
+pub fn double_tree(map: &Map) -> Vec<usize> {
+    let n = map.cities.len();
+
+    if n == 0 {
+        return Vec::new()
+    }
+
+    let parent = minimum_spanning_tree(map);
+    let mut children = vec![Vec::new(); n];
+
+    for (city, &parent) in parent.iter().enumerate().skip(1) {
+        children[parent].push(city);
+    }
+
+    // Visit the nearest child first...
+    for (city, children) in children.iter_mut().enumerate() {
+        children.sort_by(|&a, &b| map.distance(city, a).partial_cmp(&map.distance(city, b)).unwrap_or(Ordering::Equal));
+    }
+
+    let mut tour = Vec::with_capacity(n);
+    let mut stack = vec![0];
+
+    while let Some(city) = stack.pop() {
+        tour.push(city);
+        stack.extend(children[city].iter().rev());
+    }
+
+    tour
+}
+
+/// Returns the parent of each city in a minimum spanning tree
+/// rooted at the first city (Prim's algorithm). The first
+/// city is its own parent. The tree isn't directed, on an
+/// asymmetric map a path is as long as its shorter direction.
+///
+/// # Arguments
+/// * `map` - A map.
+pub fn minimum_spanning_tree(map: &Map) -> Vec<usize> {
+    let n = map.cities.len();
+    let mut parent = vec![0; n];
+    let mut in_tree = vec![false; n];
+    let mut distance = vec![f64::INFINITY; n];
+
+    if n == 0 {
+        return parent
+    }
+
+    distance[0] = 0.0;
+
+    for _ in 0..n {
+        let city = (0..n)
+            .filter(|&c| !in_tree[c])
+            .min_by(|&a, &b| distance[a].partial_cmp(&distance[b]).unwrap_or(Ordering::Equal))
+            .unwrap();
+
+        in_tree[city] = true;
+
+        for other in (0..n).filter(|&c| !in_tree[c]) {
+            let d = map.distance(city, other).min(map.distance(other, city));
+
+            if d < distance[other] {
+                distance[other] = d;
+                parent[other] = city;
+            }
+        }
+    }
+
+    parent
+}
+
+/// Returns how much longer the tour is with `c` between `a` and `b`.
+fn insertion_cost(map: &Map, a: usize, b: usize, c: usize) -> f64 {
+    map.distance(a, c) + map.distance(c, b) - map.distance(a, b)
+}
+
+/// Returns the cheapest insertion (cost, city after which it goes)
+/// of `c` in the tour that contains `start`.
+fn cheapest_edge(map: &Map, next: &[usize], start: usize, c: usize) -> (f64, usize) {
+    let mut best = (f64::INFINITY, start);
+    let mut a = start;
+
+    loop {
+        let cost = insertion_cost(map, a, next[a], c);
+
+        if cost < best.0 {
+            best = (cost, a);
+        }
+
+        a = next[a];
+
+        if a == start {
+            return best
+        }
+    }
+}
+
+/// Returns the city of `cities` nearest to `from`.
+fn nearest<I: Iterator<Item = usize>>(map: &Map, from: usize, cities: I) -> Option<usize> {
+    cities.min_by(|&a, &b| map.distance(from, a).partial_cmp(&map.distance(from, b)).unwrap_or(Ordering::Equal))
+}
+
+/// Returns the other end of the fragment that ends at `city`.
+fn fragment_end(links: &[Vec<usize>], city: usize) -> usize {
+    let (mut previous, mut current) = (usize::MAX, city);
+
+    while let Some(&next) = links[current].iter().find(|&&next| next != previous) {
+        previous = current;
+        current = next;
+    }
+
+    current
+}
+
+/// Returns the cities of a cycle where each city is linked to two others.
+fn walk(links: &[Vec<usize>], start: usize) -> Vec<usize> {
+    let mut tour = vec![start];
+    let (mut previous, mut current) = (start, links[start][0]);
+
+    while current != start {
+        tour.push(current);
+        let next = if links[current][0] != previous { links[current][0] } else { links[current][1] };
+        previous = current;
+        current = next;
+    }
+
+    tour
+}
+
+/// Returns the cities of a linked list tour.
+fn walk_next(next: &[usize], start: usize) -> Vec<usize> {
+    let mut tour = vec![start];
+    let mut current = next[start];
+
+    while current != start {
+        tour.push(current);
+        current = next[current];
+    }
+
+    tour
+}
+
+/// Disjoint sets of cities (union-find), one per fragment.
+struct Fragments {
+    parent: Vec<usize>,
+}
+
+impl Fragments {
+    fn new(n: usize) -> Fragments {
+        Fragments { parent: (0..n).collect() }
+    }
+
+    fn root(&mut self, mut city: usize) -> usize {
+        while self.parent[city] != city {
+            self.parent[city] = self.parent[self.parent[city]];
+            city = self.parent[city];
+        }
+
+        city
+    }
+
+    fn same(&mut self, a: usize, b: usize) -> bool {
+        self.root(a) == self.root(b)
+    }
+
+    /// Join the fragments of `a` and `b`, returns
+    /// `false` if they are already the same.
+    fn join(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.root(a), self.root(b));
+        self.parent[a] = b;
+        a != b
+    }
+}
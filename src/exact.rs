@@ -0,0 +1,215 @@
+use crate::construction;
+use crate::map::Map;
+
+use std::cmp::Ordering;
+
+/// The largest map `held_karp` accepts. Its memory
+/// grows as `2^n * n`, about 100 MB for 20 cities.
+pub const HELD_KARP_MAX_CITIES: usize = 20;
+
+/// An optimal tour. The exact solvers only look for closed
+/// tours, whatever the mode of the map (see `Map::mode`).
+#[derive(Clone, Debug)]
+pub struct Solution {
+    /// The cities in the order they are visited, starting from the first one.
+    pub tour: Vec<usize>,
+
+    /// The length of the tour, back to the first city included.
+    pub length: f64,
+}
+
+/// Returns an optimal tour computed with the Held-Karp dynamic
+/// programming algorithm, or `None` if the map has more than
+/// `HELD_KARP_MAX_CITIES` cities.
+///
+/// # Arguments
+/// * `map` - A map.
+pub fn held_karp(map: &Map) -> Option<Solution> {
+    let n = map.cities.len();
+
+    if n > HELD_KARP_MAX_CITIES {
+        return None
+    }
+
+    if n <= 2 {
+        return Some(solution(map, (0..n).collect()))
+    }
+
+    // The tour starts at city 0, the other cities are the bits of the
+    // sets. `cost[set * m + j]` is the length of the shortest path that
+    // starts at city 0, visits every city of `set` and ends at city `j + 1`.
+    let m = n - 1;
+    let size = 1usize << m;
+    let mut cost = vec![f64::INFINITY; size * m];
+    let mut parent = vec![u8::MAX; size * m];
+
+    for j in 0..m {
+        cost[(1 << j) * m + j] = map.distance(0, j + 1);
+    }
+
+    for set in 1..size {
+        for j in (0..m).filter(|&j| set & (1 << j) != 0) {
+            let length = cost[set * m + j];
+
+            if length == f64::INFINITY {
+                continue
+            }
+
+            for k in (0..m).filter(|&k| set & (1 << k) == 0) {
+                let next = (set | (1 << k)) * m + k;
+                let candidate = length + map.distance(j + 1, k + 1);
+
+                if candidate < cost[next] {
+                    cost[next] = candidate;
+                    parent[next] = j as u8;
+                }
+            }
+        }
+    }
+
+    let full = size - 1;
+    let mut last = (0..m)
+        .min_by(|&a, &b| {
+            let a = cost[full * m + a] + map.distance(a + 1, 0);
+            let b = cost[full * m + b] + map.distance(b + 1, 0);
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        })
+        .unwrap();
+
+    // Walk the path back from its last city...
+    let mut tour = Vec::with_capacity(n);
+    let mut set = full;
+
+    loop {
+        tour.push(last + 1);
+        let previous = parent[set * m + last];
+        set &= !(1 << last);
+
+        if previous == u8::MAX {
+            break
+        }
+
+        last = previous as usize;
+    }
+
+    tour.push(0);
+    tour.reverse();
+
+    Some(solution(map, tour))
+}
+
+/// Returns an optimal tour found by a depth-first branch and bound.
+/// A partial tour is dropped when its length plus the shortest path
+/// leaving each remaining city can't beat the best tour found so far.
+///
+/// Its running time grows exponentially with the number of cities,
+/// it is meant for maps of up to about 20 cities.
+///
+/// # Arguments
+/// * `map` - A map.
+pub fn branch_and_bound(map: &Map) -> Solution {
+    let n = map.cities.len();
+
+    if n <= 2 {
+        return solution(map, (0..n).collect())
+    }
+
+    // The cities sorted by distance from each city, to try the nearest first.
+    let nearest: Vec<Vec<usize>> = (0..n)
+        .map(|i| {
+            let mut cities: Vec<usize> = (0..n).filter(|&j| j != i).collect();
+            cities.sort_by(|&a, &b| map.distance(i, a).partial_cmp(&map.distance(i, b)).unwrap_or(Ordering::Equal));
+            cities
+        })
+        .collect();
+
+    let mut search = Search {
+        map,
+        nearest,
+        visited: vec![false; n],
+        tour: vec![0],
+        best: solution(map, construction::nearest_neighbour(map, 0)),
+    };
+
+    search.visited[0] = true;
+    search.explore(0.0);
+
+    // The length was summed in another order while exploring...
+    solution(map, search.best.tour)
+}
+
+/// The state of `branch_and_bound`.
+struct Search<'a> {
+    map: &'a Map,
+    nearest: Vec<Vec<usize>>,
+    visited: Vec<bool>,
+    tour: Vec<usize>,
+    best: Solution,
+}
+
+impl<'a> Search<'a> {
+    /// Try every way to complete the current partial tour.
+    fn explore(&mut self, length: f64) {
+        let n = self.map.cities.len();
+        let current = self.tour[self.tour.len() - 1];
+
+        if self.tour.len() == n {
+            let length = length + self.map.distance(current, 0);
+
+            if length < self.best.length {
+                self.best = Solution { tour: self.tour.clone(), length };
+            }
+
+            return
+        }
+
+        if length + self.bound(current) >= self.best.length {
+            return
+        }
+
+        for i in 0..self.nearest[current].len() {
+            let next = self.nearest[current][i];
+
+            if self.visited[next] {
+                continue
+            }
+
+            self.visited[next] = true;
+            self.tour.push(next);
+            self.explore(length + self.map.distance(current, next));
+            self.tour.pop();
+            self.visited[next] = false;
+        }
+    }
+
+    /// Returns a lower bound of the length left: the current city and
+    /// each unvisited city must be left towards an unvisited city (or
+    /// the first city, for the unvisited ones).
+    fn bound(&self, current: usize) -> f64 {
+        let shortest = |from: usize, allow_start: bool| {
+            self.nearest[from]
+                .iter()
+                .find(|&&to| !self.visited[to] || (allow_start && to == 0))
+                .map_or(0.0, |&to| self.map.distance(from, to))
+        };
+
+        let mut bound = shortest(current, false);
+
+        for city in (0..self.map.cities.len()).filter(|&city| !self.visited[city]) {
+            bound += shortest(city, true);
+        }
+
+        bound
+    }
+}
+
+/// Returns a solution with the length of the closed tour.
+fn solution(map: &Map, tour: Vec<usize>) -> Solution {
+    let mut length = 0.0;
+
+    for i in 0..tour.len() {
+        length += map.distance(tour[i], tour[(i + 1) % tour.len()]);
+    }
+
+    Solution { tour, length }
+}
@@ -0,0 +1,497 @@
+use crate::city::City;
+use crate::map::Map;
+use crate::schedule::TimeWindow;
+use crate::tsplib::{self, Instance, TsplibError};
+use crate::vrp::Fleet;
+
+use cgmath::Vector2;
+
+/// An instance loading error.
+#[derive(Debug)]
+pub struct InstanceError {
+    /// Kind of error.
+    pub kind: String,
+
+    /// The error description.
+    pub message: String,
+}
+
+impl InstanceError {
+    /// Create new instance error.
+    ///
+    /// # Arguments
+    /// * `kind` - The kind of error.
+    /// * `message` - The error description.
+    pub fn new(kind: &str, message: &str) -> InstanceError {
+        InstanceError {
+            kind: String::from(kind),
+            message: String::from(message),
+        }
+    }
+}
+
+impl From<std::io::Error> for InstanceError {
+    /// Convert an `std::io::Error` to an `InstanceError`
+    ///
+    /// # Arguments
+    /// * `error` - The error to convert.
+    fn from(error: std::io::Error) -> Self {
+        InstanceError {
+            kind: String::from("io"),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<TsplibError> for InstanceError {
+    /// Convert a `TsplibError` to an `InstanceError`
+    ///
+    /// # Arguments
+    /// * `error` - The error to convert.
+    fn from(error: TsplibError) -> Self {
+        InstanceError {
+            kind: error.kind,
+            message: error.message,
+        }
+    }
+}
+
+/// Load an instance, the format is chosen from the extension of
+/// the file: `.csv` (see `parse_csv`), `.json` (see `parse_json`).
+/// Any other file is read as a Solomon instance when it has the
+/// `VEHICLE` and `CUSTOMER` headers (see `parse_solomon`), or as a
+/// TSPLIB problem file otherwise.
+///
+/// # Arguments
+/// * `path` - The path of the file.
+pub fn load_instance(path: &str) -> Result<Instance, InstanceError> {
+    let content = std::fs::read_to_string(path)?;
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase());
+
+    let mut instance = match extension.as_deref() {
+        Some("csv") => parse_csv(&content)?,
+        Some("json") => parse_json(&content)?,
+        _ if is_solomon(&content) => parse_solomon(&content)?,
+        _ => tsplib::parse_tsp(&content)?,
+    };
+
+    if instance.name.is_empty() {
+        let stem = std::path::Path::new(path).file_stem().and_then(|stem| stem.to_str());
+        instance.name = String::from(stem.unwrap_or_default());
+    }
+
+    Ok(instance)
+}
+
+/// Parse cities written as `x,y` (or `id,x,y`) lines. A first line
+/// that doesn't start with a number is taken as a header, and the
+/// empty lines and the lines starting with `#` are ignored.
+///
+/// # Arguments
+/// * `content` - The content of the file.
+pub fn parse_csv(content: &str) -> Result<Instance, InstanceError> {
+    let mut cities = Vec::new();
+
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue
+        }
+
+        let values: Vec<&str> = line.split([',', ';', '\t']).map(|value| value.trim()).collect();
+
+        if cities.is_empty() && values[0].parse::<f64>().is_err() {
+            continue
+        }
+
+        let (x, y) = match values.len() {
+            2 => (values[0], values[1]),
+            3 => (values[1], values[2]),
+            count => {
+                return Err(InstanceError::new(
+                    "format",
+                    &format!("line {}: 2 or 3 columns expected, {} found", number + 1, count),
+                ))
+            }
+        };
+
+        let coordinate = |value: &str| {
+            value.parse::<f32>().map_err(|_| {
+                InstanceError::new("format", &format!("line {}: invalid number `{}`", number + 1, value))
+            })
+        };
+
+        cities.push(City::new(Vector2::new(coordinate(x)?, coordinate(y)?)));
+    }
+
+    instance(String::new(), String::new(), cities)
+}
+
+/// Parse cities written in JSON, either as an array of `[x, y]`
+/// arrays or `{"x": .., "y": ..}` objects, or as an object with
+/// such an array in `cities` and optionally a `name` and a `comment`.
+///
+/// # Arguments
+/// * `content` - The content of the file.
+pub fn parse_json(content: &str) -> Result<Instance, InstanceError> {
+    let mut parser = JsonParser { chars: content.chars().collect(), index: 0, depth: 0 };
+    let value = parser.parse_document()?;
+
+    let (name, comment, cities) = match &value {
+        Json::Object(fields) => {
+            let text = |key: &str| match field(fields, key) {
+                Some(Json::String(text)) => text.clone(),
+                _ => String::new(),
+            };
+            let cities = field(fields, "cities")
+                .ok_or_else(|| InstanceError::new("format", "the `cities` array is missing"))?;
+
+            (text("name"), text("comment"), cities)
+        }
+        _ => (String::new(), String::new(), &value),
+    };
+
+    let cities = match cities {
+        Json::Array(cities) => cities,
+        _ => return Err(InstanceError::new("format", "`cities` must be an array")),
+    };
+
+    let mut positions = Vec::with_capacity(cities.len());
+
+    for (i, city) in cities.iter().enumerate() {
+        let position = match city {
+            Json::Array(values) if values.len() == 2 => (values[0].number(), values[1].number()),
+            Json::Object(fields) => (
+                field(fields, "x").and_then(Json::number),
+                field(fields, "y").and_then(Json::number),
+            ),
+            _ => (None, None),
+        };
+
+        match position {
+            (Some(x), Some(y)) => positions.push(City::new(Vector2::new(x as f32, y as f32))),
+            _ => {
+                return Err(InstanceError::new(
+                    "format",
+                    &format!("city {} must be `[x, y]` or `{{\"x\": .., \"y\": ..}}`", i),
+                ))
+            }
+        }
+    }
+
+    instance(name, comment, positions)
+}
+
+/// Parse a vehicle routing problem with time windows in the format
+/// of the Solomon benchmark: the name on the first line, the number
+/// of vehicles and their capacity after `VEHICLE`, then one line per
+/// city after `CUSTOMER`: its number, `x`, `y`, demand, ready time,
+/// due date and service time. Customer `0` is the depot. The number
+/// of vehicles is only kept in the comment, the colony may use more.
+///
+/// # Arguments
+/// * `content` - The content of the file.
+pub fn parse_solomon(content: &str) -> Result<Instance, InstanceError> {
+    let mut lines = content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+    let name = lines.next().map(|(_, line)| String::from(line.trim())).unwrap_or_default();
+    let mut vehicles = None;
+    let mut cities = Vec::new();
+
+    // The lines of the numbers, the headers are skipped.
+    let numbers = lines.filter(|(_, line)| line.trim().starts_with(|c: char| c.is_ascii_digit() || c == '-'));
+
+    for (number, line) in numbers {
+        let values = line
+            .split_whitespace()
+            .map(|value| {
+                value.parse::<f64>().map_err(|_| {
+                    InstanceError::new("format", &format!("line {}: invalid number `{}`", number + 1, value))
+                })
+            })
+            .collect::<Result<Vec<f64>, _>>()?;
+
+        match (vehicles, values.as_slice()) {
+            (None, &[count, capacity]) => vehicles = Some((count, capacity)),
+            (Some(_), &[customer, x, y, demand, ready, due, service]) => {
+                if customer as usize != cities.len() {
+                    return Err(InstanceError::new(
+                        "format",
+                        &format!("line {}: customer {} expected, {} found", number + 1, cities.len(), customer),
+                    ));
+                }
+
+                let mut city = City::with_demand(Vector2::new(x as f32, y as f32), demand);
+                city.time_window = Some(TimeWindow::new(ready, due));
+                city.service_time = service;
+                cities.push(city);
+            }
+            _ => {
+                return Err(InstanceError::new(
+                    "format",
+                    &format!("line {}: {} values found", number + 1, values.len()),
+                ))
+            }
+        }
+    }
+
+    let (count, capacity) = vehicles.ok_or_else(|| InstanceError::new("format", "the vehicles are missing"))?;
+    let mut instance = instance(name, format!("{} vehicles", count), cities)?;
+    instance.map.fleet = Some(Fleet::new(0, capacity));
+
+    Ok(instance)
+}
+
+/// Returns `true` if the content has the headers of a Solomon instance.
+fn is_solomon(content: &str) -> bool {
+    let mut headers = content.lines().map(|line| line.trim());
+
+    headers.any(|line| line == "VEHICLE") && headers.any(|line| line.starts_with("CUSTOMER"))
+}
+
+/// Returns an instance of the cities.
+fn instance(name: String, comment: String, cities: Vec<City>) -> Result<Instance, InstanceError> {
+    if cities.is_empty() {
+        return Err(InstanceError::new("format", "the instance contains no city"));
+    }
+
+    Ok(Instance {
+        name,
+        comment,
+        map: Map::new(&cities),
+    })
+}
+
+/// A JSON value.
+enum Json {
+    /// `true`, `false` or `null`, none of them is used by the instances.
+    Literal,
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Returns the value if it is a number.
+    fn number(&self) -> Option<f64> {
+        match self {
+            Json::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the value of a field of a JSON object.
+fn field<'a>(fields: &'a [(String, Json)], key: &str) -> Option<&'a Json> {
+    fields.iter().find(|(name, _)| name == key).map(|(_, value)| value)
+}
+
+/// The deepest nesting of arrays and objects accepted by the JSON parser,
+/// the instances only need three levels.
+const MAX_JSON_DEPTH: usize = 64;
+
+/// A minimal JSON parser.
+struct JsonParser {
+    chars: Vec<char>,
+    index: usize,
+
+    /// The number of arrays and objects being parsed.
+    depth: usize,
+}
+
+impl JsonParser {
+    /// Parse a whole document, which must contain a single value.
+    fn parse_document(&mut self) -> Result<Json, InstanceError> {
+        let value = self.parse_value()?;
+        self.skip_whitespace();
+
+        if self.index < self.chars.len() {
+            return Err(self.error("unexpected character after the value"));
+        }
+
+        Ok(value)
+    }
+
+    fn parse_value(&mut self) -> Result<Json, InstanceError> {
+        self.skip_whitespace();
+
+        match self.chars.get(self.index) {
+            Some('{') | Some('[') => self.parse_nested(),
+            Some('"') => self.parse_string().map(Json::String),
+            Some('t') => self.parse_literal("true"),
+            Some('f') => self.parse_literal("false"),
+            Some('n') => self.parse_literal("null"),
+            Some(_) => self.parse_number(),
+            None => Err(self.error("unexpected end of the document")),
+        }
+    }
+
+    /// Parse an array or an object, without going deeper than `MAX_JSON_DEPTH`.
+    fn parse_nested(&mut self) -> Result<Json, InstanceError> {
+        if self.depth == MAX_JSON_DEPTH {
+            return Err(self.error("arrays and objects are nested too deeply"));
+        }
+
+        self.depth += 1;
+        let value = match self.chars[self.index] {
+            '{' => self.parse_object()?,
+            _ => self.parse_array()?,
+        };
+        self.depth -= 1;
+
+        Ok(value)
+    }
+
+    fn parse_object(&mut self) -> Result<Json, InstanceError> {
+        let mut fields = Vec::new();
+        self.index += 1;
+        self.skip_whitespace();
+
+        if self.chars.get(self.index) == Some(&'}') {
+            self.index += 1;
+            return Ok(Json::Object(fields))
+        }
+
+        loop {
+            self.skip_whitespace();
+
+            if self.chars.get(self.index) != Some(&'"') {
+                return Err(self.error("a field name is expected"));
+            }
+
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            fields.push((key, self.parse_value()?));
+            self.skip_whitespace();
+
+            match self.chars.get(self.index) {
+                Some(',') => self.index += 1,
+                Some('}') => {
+                    self.index += 1;
+                    return Ok(Json::Object(fields))
+                }
+                _ => return Err(self.error("`,` or `}` is expected")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Json, InstanceError> {
+        let mut values = Vec::new();
+        self.index += 1;
+        self.skip_whitespace();
+
+        if self.chars.get(self.index) == Some(&']') {
+            self.index += 1;
+            return Ok(Json::Array(values))
+        }
+
+        loop {
+            values.push(self.parse_value()?);
+            self.skip_whitespace();
+
+            match self.chars.get(self.index) {
+                Some(',') => self.index += 1,
+                Some(']') => {
+                    self.index += 1;
+                    return Ok(Json::Array(values))
+                }
+                _ => return Err(self.error("`,` or `]` is expected")),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, InstanceError> {
+        let mut text = String::new();
+        self.index += 1;
+
+        loop {
+            let c = *self.chars.get(self.index).ok_or_else(|| self.error("unterminated string"))?;
+            self.index += 1;
+
+            match c {
+                '"' => return Ok(text),
+                '\\' => {
+                    let escaped = *self.chars.get(self.index).ok_or_else(|| self.error("unterminated string"))?;
+                    self.index += 1;
+
+                    text.push(match escaped {
+                        '"' | '\\' | '/' => escaped,
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => {
+                            let digits: String = self.chars.iter().skip(self.index).take(4).collect();
+                            self.index += 4;
+                            u32::from_str_radix(&digits, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .unwrap_or(char::REPLACEMENT_CHARACTER)
+                        }
+                        _ => return Err(self.error("invalid escape sequence")),
+                    });
+                }
+                _ => text.push(c),
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Json, InstanceError> {
+        let start = self.index;
+
+        while let Some(c) = self.chars.get(self.index) {
+            if c.is_ascii_digit() || "+-.eE".contains(*c) {
+                self.index += 1;
+            } else {
+                break
+            }
+        }
+
+        let text: String = self.chars[start..self.index].iter().collect();
+        text.parse::<f64>().map(Json::Number).map_err(|_| {
+            self.index = start;
+            self.error("invalid value")
+        })
+    }
+
+    fn parse_literal(&mut self, literal: &str) -> Result<Json, InstanceError> {
+        let text: String = self.chars.iter().skip(self.index).take(literal.len()).collect();
+
+        if text != literal {
+            return Err(self.error("invalid value"));
+        }
+
+        self.index += literal.len();
+        Ok(Json::Literal)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), InstanceError> {
+        if self.chars.get(self.index) != Some(&expected) {
+            return Err(self.error(&format!("`{}` is expected", expected)));
+        }
+
+        self.index += 1;
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.index).is_some_and(|c| c.is_whitespace()) {
+            self.index += 1;
+        }
+    }
+
+    /// Returns an error at the current position (line and column).
+    fn error(&self, message: &str) -> InstanceError {
+        let before = &self.chars[..self.index.min(self.chars.len())];
+        let line = before.iter().filter(|&&c| c == '\n').count() + 1;
+        let column = before.iter().rev().take_while(|&&c| c != '\n').count() + 1;
+
+        InstanceError::new("format", &format!("line {}, column {}: {}", line, column, message))
+    }
+}
@@ -1,8 +1,24 @@
 pub mod window;
 pub mod shader;
 pub mod ant;
+pub mod bounds;
+pub mod checkpoint;
 pub mod circle;
+pub mod colony;
+pub mod construction;
+pub mod exact;
 pub mod city;
+pub mod instance;
+pub mod local_search;
 pub mod map;
+pub mod matrix;
+pub mod observer;
+pub mod params;
+pub mod schedule;
+pub mod spatial;
+pub mod stats;
+pub mod strategy;
+pub mod tsplib;
+pub mod vrp;
 pub mod mesh;
 pub mod vertex;
\ No newline at end of file
@@ -0,0 +1,464 @@
+use crate::map::{Map, TourMode};
+use crate::vrp;
+
+use std::collections::VecDeque;
+
+/// Moves that improve a tour by less than this are ignored,
+/// so rounding errors can't make the search loop forever.
+pub(crate) const EPSILON: f64 = 1e-9;
+
+/// Marks a city that is not part of the tour.
+const ABSENT: usize = usize::MAX;
+
+/// Which tours of an iteration are improved by the local search.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LocalSearchScope {
+    /// The tours are kept as the ants walked them.
+    None,
+
+    /// The tour of every ant is improved.
+    EveryAnt,
+
+    /// Only the shortest tour of the iteration is improved.
+    IterationBest,
+}
+
+/// The moves used to improve the tours walked by the ants.
+#[derive(Clone, Debug)]
+pub struct LocalSearch {
+    /// Which tours are improved.
+    pub scope: LocalSearchScope,
+
+    /// Replace two paths by two shorter ones (reversing the cities between
+    /// them). Ignored on an asymmetric map, where reversing the cities
+    /// changes the length of every path between them.
+    pub two_opt: bool,
+
+    /// Move one, two or three consecutive cities elsewhere in the tour.
+    pub or_opt: bool,
+
+    /// Move a segment of up to `segment_length` cities elsewhere in the
+    /// tour, reversed or not (a special case of 3-opt). The segments are
+    /// never reversed on an asymmetric map.
+    pub segment_insertion: bool,
+
+    /// The longest segment moved by `segment_insertion`.
+    pub segment_length: usize,
+
+    /// Move or swap cities between two routes of a vehicle routing
+    /// problem (see `vrp::improve_routes`).
+    pub inter_route: bool,
+
+    /// How many nearest neighbours of a city are
+    /// considered when looking for a move.
+    pub neighbours: usize,
+}
+
+impl Default for LocalSearch {
+    fn default() -> Self {
+        LocalSearch {
+            scope: LocalSearchScope::None,
+            two_opt: true,
+            or_opt: true,
+            segment_insertion: false,
+            segment_length: 30,
+            inter_route: true,
+            neighbours: 10,
+        }
+    }
+}
+
+impl LocalSearch {
+    /// Apply the enabled moves until none of them can shorten the tour.
+    /// A closed tour still starts at the same city afterwards, and a
+    /// fixed path still goes from its `start` to its `end` (see `Map::mode`).
+    /// The routes of a vehicle routing problem are improved by
+    /// `vrp::improve_routes` instead. Returns `true` if the tour was improved.
+    ///
+    /// The moves only look at the length of the tour: when some cities
+    /// have a time window, the improved tour is only kept if its cost
+    /// (see `Map::tour_cost`) is lower, the tour is left as is otherwise.
+    ///
+    /// # Arguments
+    /// * `map` - A map.
+    /// * `neighbours` - The nearest neighbours of each city (see `Map::nearest_neighbours`).
+    /// * `tour` - The tour to improve.
+    pub fn improve(&self, map: &Map, neighbours: &[Vec<usize>], tour: &mut Vec<usize>) -> bool {
+        if !map.has_time_windows() {
+            return self.shorten(map, neighbours, tour)
+        }
+
+        let original = tour.clone();
+        let cost = map.tour_cost(tour);
+
+        if self.shorten(map, neighbours, tour) && map.tour_cost(tour) < cost - EPSILON {
+            return true
+        }
+
+        *tour = original;
+        false
+    }
+
+    /// Apply the enabled moves until none of them can shorten the tour
+    /// (see `LocalSearch::improve`). Returns `true` if the tour is shorter.
+    fn shorten(&self, map: &Map, neighbours: &[Vec<usize>], tour: &mut Vec<usize>) -> bool {
+        if let Some(fleet) = &map.fleet {
+            return vrp::improve_routes(self, map, fleet, neighbours, tour)
+        }
+
+        let first = match tour.first() {
+            Some(&first) => first,
+            None => return false,
+        };
+
+        // A path is improved as a tour closed by a virtual city
+        // (see `distance`), which is then taken out again.
+        let closed = map.mode == TourMode::Closed;
+
+        if !closed {
+            tour.push(map.cities.len());
+        }
+
+        let mut improved = false;
+
+        loop {
+            let mut changed = false;
+
+            if self.two_opt {
+                changed |= two_opt(map, tour, neighbours);
+            }
+
+            if self.or_opt {
+                changed |= or_opt(map, tour, neighbours);
+            }
+
+            if self.segment_insertion {
+                changed |= segment_insertion(map, tour, neighbours, self.segment_length);
+            }
+
+            if !changed {
+                break
+            }
+
+            improved = true;
+        }
+
+        if closed {
+            if let Some(start) = tour.iter().position(|&city| city == first) {
+                tour.rotate_left(start);
+            }
+        } else if let Some(virtual_city) = tour.iter().position(|&city| city == map.cities.len()) {
+            tour.rotate_left(virtual_city + 1);
+            tour.pop();
+
+            // The path may have been walked backward...
+            if let TourMode::Fixed { start, .. } = map.mode {
+                if tour.first() != Some(&start) {
+                    tour.reverse();
+                }
+            }
+        }
+
+        improved
+    }
+}
+
+/// Improve a closed tour with 2-opt moves until none is left. Only
+/// the nearest neighbours of a city are tried, and a city is checked
+/// again only when one of its paths changed (don't-look bits).
+/// Returns `true` if the tour was improved, always `false` on an
+/// asymmetric map.
+///
+/// # Arguments
+/// * `map` - A map.
+/// * `tour` - The tour to improve, it may only contain a part of the cities.
+/// * `neighbours` - The nearest neighbours of each city, nearest first.
+pub fn two_opt(map: &Map, tour: &mut [usize], neighbours: &[Vec<usize>]) -> bool {
+    let size = tour.len();
+
+    if size < 4 || !map.is_symmetric() {
+        return false
+    }
+
+    let mut position = positions(map, tour);
+    let mut queue: VecDeque<usize> = tour.iter().copied().collect();
+    let mut queued = vec![false; map.cities.len() + 1];
+    let mut improved = false;
+
+    for &city in tour.iter() {
+        queued[city] = true;
+    }
+
+    while let Some(a) = queue.pop_front() {
+        queued[a] = false;
+
+        'search: for &forward in [true, false].iter() {
+            // Replace the paths (a, b) and (c, d) by (a, c) and (b, d),
+            // where b and d follow (or precede) a and c in the tour.
+            let step = |index: usize| if forward { (index + 1) % size } else { (index + size - 1) % size };
+
+            let b = tour[step(position[a])];
+            let ab = distance(map, a, b);
+
+            for &c in neighbours_of(neighbours, a).iter() {
+                let ac = distance(map, a, c);
+
+                if ac >= ab {
+                    break
+                }
+
+                if position[c] == ABSENT {
+                    continue
+                }
+
+                let d = tour[step(position[c])];
+
+                if c == b || d == a {
+                    continue
+                }
+
+                if ab + distance(map, c, d) - ac - distance(map, b, d) > EPSILON {
+                    let (from, to) = if forward { (position[b], position[c]) } else { (position[a], position[d]) };
+                    reverse(tour, &mut position, from, to);
+
+                    for &city in [a, b, c, d].iter() {
+                        if !queued[city] {
+                            queued[city] = true;
+                            queue.push_back(city);
+                        }
+                    }
+
+                    improved = true;
+                    break 'search;
+                }
+            }
+        }
+    }
+
+    improved
+}
+
+/// Improve a closed tour by moving one, two or three consecutive
+/// cities elsewhere, keeping their order. Returns `true` if the
+/// tour was improved.
+///
+/// # Arguments
+/// * `map` - A map.
+/// * `tour` - The tour to improve, it may only contain a part of the cities.
+/// * `neighbours` - The nearest neighbours of each city, nearest first.
+pub fn or_opt(map: &Map, tour: &mut Vec<usize>, neighbours: &[Vec<usize>]) -> bool {
+    move_segments(map, tour, neighbours, 3, false)
+}
+
+/// Improve a closed tour by moving a segment of consecutive cities
+/// elsewhere, reversed or not (only on a symmetric map). Returns
+/// `true` if the tour was improved.
+///
+/// # Arguments
+/// * `map` - A map.
+/// * `tour` - The tour to improve, it may only contain a part of the cities.
+/// * `neighbours` - The nearest neighbours of each city, nearest first.
+/// * `max_length` - The longest segment that can be moved.
+pub fn segment_insertion(map: &Map, tour: &mut Vec<usize>, neighbours: &[Vec<usize>], max_length: usize) -> bool {
+    move_segments(map, tour, neighbours, max_length, map.is_symmetric())
+}
+
+/// Returns the position of each city (and of the virtual
+/// city) in the tour, `ABSENT` for the cities that aren't in the tour.
+fn positions(map: &Map, tour: &[usize]) -> Vec<usize> {
+    let mut position = vec![ABSENT; map.cities.len() + 1];
+
+    for (i, &city) in tour.iter().enumerate() {
+        position[city] = i;
+    }
+
+    position
+}
+
+/// Reverse the cities from position `from` to position `to`
+/// (going forward, wrapping around the end of the tour). The
+/// shortest of the segment and its complement is reversed.
+fn reverse(tour: &mut [usize], position: &mut [usize], from: usize, to: usize) {
+    let size = tour.len();
+    let mut length = (to + size - from) % size + 1;
+    let (mut i, mut j) = (from, to);
+
+    if 2 * length > size {
+        i = (to + 1) % size;
+        j = (from + size - 1) % size;
+        length = size - length;
+    }
+
+    for _ in 0..length / 2 {
+        tour.swap(i, j);
+        position[tour[i]] = i;
+        position[tour[j]] = j;
+        i = (i + 1) % size;
+        j = (j + size - 1) % size;
+    }
+}
+
+/// Try to move each segment of up to `max_length` cities between
+/// two other consecutive cities, next to a neighbour of one of its ends.
+fn move_segments(map: &Map, tour: &mut Vec<usize>, neighbours: &[Vec<usize>], max_length: usize, reversible: bool) -> bool {
+    let size = tour.len();
+
+    if size < 4 {
+        return false
+    }
+
+    let max_length = max_length.min(size - 3);
+    let mut position = positions(map, tour);
+    let mut improved = false;
+
+    loop {
+        let mut moved = false;
+
+        for start in 0..size {
+            for length in 1..=max_length {
+                let end = (start + length - 1) % size;
+                let (first, last) = (tour[start], tour[end]);
+                let before = tour[(start + size - 1) % size];
+                let after = tour[(end + 1) % size];
+
+                let in_segment = |index: usize| index != ABSENT && (index + size - start) % size < length;
+
+                // What is saved by taking the segment out of the tour...
+                let removal = distance(map, before, first) + distance(map, last, after) - distance(map, before, after);
+
+                if removal <= EPSILON {
+                    continue
+                }
+
+                let orientations: &[(usize, usize)] = if reversible && length > 1 {
+                    &[(first, last), (last, first)]
+                } else {
+                    &[(first, last)]
+                };
+
+                let mut insertion = None;
+
+                // ... must be more than what it cost to put it
+                // back between two consecutive cities c and e.
+                'search: for &(head, tail) in orientations.iter() {
+                    for &c in neighbours_of(neighbours, head).iter() {
+                        if distance(map, c, head) >= removal {
+                            // The neighbours are sorted by the distance from `head`,
+                            // which is the distance to `head` only on a symmetric map.
+                            if map.is_symmetric() {
+                                break
+                            }
+
+                            continue
+                        }
+
+                        if position[c] == ABSENT || in_segment(position[c]) {
+                            continue
+                        }
+
+                        let e = tour[(position[c] + 1) % size];
+
+                        if !in_segment(position[e]) && insertion_gain(map, removal, c, head, tail, e) > EPSILON {
+                            insertion = Some((c, head != first));
+                            break 'search;
+                        }
+                    }
+
+                    for &e in neighbours_of(neighbours, tail).iter() {
+                        if distance(map, tail, e) >= removal {
+                            break
+                        }
+
+                        if position[e] == ABSENT || in_segment(position[e]) {
+                            continue
+                        }
+
+                        let c = tour[(position[e] + size - 1) % size];
+
+                        if !in_segment(position[c]) && insertion_gain(map, removal, c, head, tail, e) > EPSILON {
+                            insertion = Some((c, head != first));
+                            break 'search;
+                        }
+                    }
+                }
+
+                if let Some((c, reversed)) = insertion {
+                    insert_segment(tour, start, length, c, reversed);
+
+                    for (i, &city) in tour.iter().enumerate() {
+                        position[city] = i;
+                    }
+
+                    moved = true;
+                    break
+                }
+            }
+        }
+
+        if !moved {
+            break
+        }
+
+        improved = true;
+    }
+
+    improved
+}
+
+/// Returns the distance from a city of a tour to another. The city
+/// `map.cities.len()` is the virtual city that closes a path: the
+/// paths between it and the ends of the path are free, and the
+/// other paths to it can't be taken.
+fn distance(map: &Map, from: usize, to: usize) -> f64 {
+    let virtual_city = map.cities.len();
+
+    if from != virtual_city && to != virtual_city {
+        return map.distance(from, to)
+    }
+
+    match map.mode {
+        TourMode::Fixed { start, end } => {
+            let free = from == end || to == start || (map.is_symmetric() && (from == start || to == end));
+
+            if free {
+                0.0
+            } else {
+                f64::INFINITY
+            }
+        }
+        _ => 0.0,
+    }
+}
+
+/// Returns the nearest neighbours of a city. The virtual city
+/// has none, nor any city when `neighbours` is empty.
+pub(crate) fn neighbours_of(neighbours: &[Vec<usize>], city: usize) -> &[usize] {
+    match neighbours.get(city) {
+        Some(neighbours) => neighbours,
+        None => &[],
+    }
+}
+
+/// Returns how much shorter is the tour when a segment (taken out
+/// with a saving of `removal`) is put back between `c` and `e`.
+fn insertion_gain(map: &Map, removal: f64, c: usize, head: usize, tail: usize, e: usize) -> f64 {
+    removal - (distance(map, c, head) + distance(map, tail, e) - distance(map, c, e))
+}
+
+/// Move the `length` cities starting at position `start` after the city `c`.
+fn insert_segment(tour: &mut Vec<usize>, start: usize, length: usize, c: usize, reversed: bool) {
+    let size = tour.len();
+    let mut segment: Vec<usize> = (0..length).map(|k| tour[(start + k) % size]).collect();
+    let rest: Vec<usize> = (0..size - length).map(|k| tour[(start + length + k) % size]).collect();
+
+    if reversed {
+        segment.reverse();
+    }
+
+    let at = rest.iter().position(|&city| city == c).unwrap() + 1;
+
+    tour.clear();
+    tour.extend_from_slice(&rest[..at]);
+    tour.extend_from_slice(&segment);
+    tour.extend_from_slice(&rest[at..]);
+}
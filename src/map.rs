@@ -1,51 +1,449 @@
 use crate::city::City;
+use crate::matrix::SquareMatrix;
+use crate::params::AcoParams;
+use crate::schedule;
+use crate::spatial::Grid;
+use crate::vrp::Fleet;
+
+use std::cmp::Ordering;
+
+/// How the cities of a tour are linked together.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TourMode {
+    /// The tour goes back from its last city to its first one.
+    #[default]
+    Closed,
+
+    /// The tour is a path that may start and end at any city.
+    Open,
+
+    /// The tour is a path from the city `start` to the city `end`.
+    Fixed { start: usize, end: usize },
+}
 
 pub struct Map {
     pub cities: Vec<City>,
-    pub pheromones: Vec<Vec<usize>>,
+
+    /// How the cities of a tour are linked, the
+    /// tours are closed unless told otherwise.
+    pub mode: TourMode,
+
+    /// The vehicles that deliver the cities, when the map is a vehicle
+    /// routing problem. The tours are then giant tours (see `Fleet`).
+    pub fleet: Option<Fleet>,
+
+    /// What each unit of lateness adds to the cost of a tour
+    /// when some cities have a time window (see `Map::tour_cost`).
+    pub lateness_penalty: f64,
+
+    /// The amount of pheromone left on the path
+    /// between two cities.
+    pub pheromones: Vec<Vec<f64>>,
+
+    /// The distance between two cities, which may depend
+    /// on the direction (see `Map::is_symmetric`).
+    distances: SquareMatrix<f64>,
+
+    /// The heuristic value `(1 / distance) ^ beta`
+    /// of the path between two cities.
+    heuristics: SquareMatrix<f64>,
+
+    /// The nearest cities of each city, nearest first. The
+    /// ants only look at them when choosing the next city.
+    candidates: Vec<Vec<usize>>,
+
+    /// `true` when the nearest cities by position are
+    /// also the nearest ones by distance.
+    pub(crate) geometric: bool,
 }
 
 impl Map {
+    /// Create new map. The distance between each pair
+    /// of cities is computed once here.
+    ///
+    /// # Arguments
+    /// * `cities` - The cities of the map.
     pub fn new(cities: &[City]) -> Map {
+        let distances = SquareMatrix::symmetric(cities.len(), |i, j| {
+            let delta = cities[i].position - cities[j].position;
+            (delta.x as f64).hypot(delta.y as f64)
+        });
+
+        let mut map = Map::with_distances(cities, distances);
+        map.geometric = true;
+        map
+    }
+
+    /// Create new map where the distance between two cities
+    /// is given instead of computed from their position. With
+    /// a matrix created by `SquareMatrix::full`, the distance
+    /// from `i` to `j` may differ from the distance from `j`
+    /// to `i` (an asymmetric traveling salesman problem).
+    ///
+    /// # Arguments
+    /// * `cities` - The cities of the map.
+    /// * `distances` - The distance between each pair of cities.
+    pub fn with_distances(cities: &[City], distances: SquareMatrix<f64>) -> Map {
+        assert_eq!(cities.len(), distances.size(), "one row of distances is needed per city");
+
         let mut pheromones = Vec::new();
 
         for _ in 0..cities.len() {
             let mut paths = Vec::new();
-            
+
             for _ in 0..cities.len() {
-                paths.push(1usize)
+                paths.push(1.0)
             }
 
             pheromones.push(paths);
         }
 
+        let heuristics = Map::compute_heuristics(&distances, AcoParams::default().beta);
+
         Map {
             cities: cities.to_vec(),
-            pheromones: pheromones,
+            mode: TourMode::Closed,
+            fleet: None,
+            lateness_penalty: 100.0,
+            pheromones,
+            distances,
+            heuristics,
+            candidates: Vec::new(),
+            geometric: false,
         }
     }
 
-    pub fn best_path(&self, from: usize) -> Vec<usize> {
-        let mut already_visited = Vec::new();
-        let mut path = Vec::new();
+    /// Returns `true` if the distance between two cities
+    /// is the same in both directions. The pheromones of a
+    /// path are then the same in both directions too.
+    pub fn is_symmetric(&self) -> bool {
+        self.distances.is_symmetric()
+    }
 
-        already_visited.push(from);
-        path.push(from);
+    /// Returns `true` if some cities can only be served
+    /// at some times (see `City::time_window`).
+    pub fn has_time_windows(&self) -> bool {
+        self.cities.iter().any(|city| city.time_window.is_some())
+    }
 
-        while already_visited.len() < self.cities.len() {
-            let (mut best, mut index) = (self.pheromones[from][from], from);
+    /// Returns the distance from a city to another.
+    ///
+    /// # Arguments
+    /// * `from` - The index of the first city.
+    /// * `to` - The index of the second city.
+    pub fn distance(&self, from: usize, to: usize) -> f64 {
+        self.distances.get(from, to)
+    }
+
+    /// Returns the heuristic value `(1 / distance) ^ beta`
+    /// of the path between two cities.
+    ///
+    /// # Arguments
+    /// * `from` - The index of the first city.
+    /// * `to` - The index of the second city.
+    pub fn heuristic(&self, from: usize, to: usize) -> f64 {
+        self.heuristics.get(from, to)
+    }
 
-            for i in 0..self.pheromones[from].len() {
-                if self.pheromones[from][i] > best && !already_visited.contains(&i) {
-                    best = self.pheromones[from][i];
-                    index = i;
+    /// Compute again the heuristic value of each path
+    /// with another `beta` exponent.
+    ///
+    /// # Arguments
+    /// * `beta` - How much the distance to a city repels an ant.
+    pub fn set_beta(&mut self, beta: f64) {
+        self.heuristics = Map::compute_heuristics(&self.distances, beta);
+    }
+
+    /// Returns the `count` nearest cities of each city, from the
+    /// nearest to the farthest. A spatial grid is used when the
+    /// distances come from the position of the cities.
+    ///
+    /// # Arguments
+    /// * `count` - The number of neighbours per city.
+    pub fn nearest_neighbours(&self, count: usize) -> Vec<Vec<usize>> {
+        let size = self.cities.len();
+        let count = count.min(size.saturating_sub(1));
+        let by_distance = |i: usize| move |&a: &usize, &b: &usize| {
+            self.distance(i, a).partial_cmp(&self.distance(i, b)).unwrap_or(Ordering::Equal)
+        };
+
+        if self.geometric {
+            let positions: Vec<_> = self.cities.iter().map(|city| city.position).collect();
+            let grid = Grid::new(&positions);
+
+            return (0..size)
+                .map(|i| {
+                    let mut nearest = grid.nearest(i, count);
+                    // Rounded distances may slightly change the order...
+                    nearest.sort_by(by_distance(i));
+                    nearest
+                })
+                .collect()
+        }
+
+        (0..size)
+            .map(|i| {
+                let mut others: Vec<usize> = (0..size).filter(|&j| j != i).collect();
+
+                if count > 0 && count < others.len() {
+                    others.select_nth_unstable_by(count - 1, by_distance(i));
                 }
+
+                others.truncate(count);
+                others.sort_by(by_distance(i));
+                others
+            })
+            .collect()
+    }
+
+    /// Compute the candidate list of each city: the ants only
+    /// look at the `count` nearest cities when choosing where to
+    /// go next. A `count` of `0` removes the candidate lists.
+    ///
+    /// # Arguments
+    /// * `count` - The number of candidates per city.
+    pub fn build_candidates(&mut self, count: usize) {
+        self.candidates = if count == 0 {
+            Vec::new()
+        } else {
+            self.nearest_neighbours(count)
+        };
+    }
+
+    /// Returns the candidate list of a city, empty when
+    /// the candidate lists aren't built.
+    ///
+    /// # Arguments
+    /// * `city` - The index of the city.
+    pub fn candidates(&self, city: usize) -> &[usize] {
+        match self.candidates.get(city) {
+            Some(candidates) => candidates,
+            None => &[],
+        }
+    }
+
+    /// Returns the matrix of `(1 / distance) ^ beta`.
+    fn compute_heuristics(distances: &SquareMatrix<f64>, beta: f64) -> SquareMatrix<f64> {
+        distances.map(|distance| (1.0 / distance).powf(beta))
+    }
+
+    /// Set the same amount of pheromones on every path of the map.
+    ///
+    /// # Arguments
+    /// * `value` - The amount of pheromones.
+    pub fn reset_pheromones(&mut self, value: f64) {
+        for paths in self.pheromones.iter_mut() {
+            for pheromone in paths.iter_mut() {
+                *pheromone = value;
+            }
+        }
+    }
+
+    /// Move the pheromones of each path of a tour (see `Map::paths`)
+    /// toward a value: `pheromone = (1 - weight) * pheromone + weight * value`.
+    /// On an asymmetric map, only the direction walked is changed.
+    ///
+    /// # Arguments
+    /// * `tour` - The index of each city in the order they are visited.
+    /// * `weight` - How much the pheromones move toward `value`, between `0.0` and `1.0`.
+    /// * `value` - The value toward which the pheromones move.
+    pub fn blend_pheromones(&mut self, tour: &[usize], weight: f64, value: f64) {
+        for (from, to) in self.paths(tour) {
+            let pheromone = (1.0 - weight) * self.pheromones[from][to] + weight * value;
+            self.pheromones[from][to] = pheromone;
+
+            if self.is_symmetric() {
+                self.pheromones[to][from] = pheromone;
             }
+        }
+    }
+
+    /// Keep the pheromones of every path between two limits.
+    ///
+    /// # Arguments
+    /// * `min` - The lowest amount of pheromones on a path.
+    /// * `max` - The highest amount of pheromones on a path.
+    pub fn clamp_pheromones(&mut self, min: f64, max: f64) {
+        for paths in self.pheromones.iter_mut() {
+            for pheromone in paths.iter_mut() {
+                *pheromone = pheromone.max(min).min(max);
+            }
+        }
+    }
+
+    /// Returns the average lambda-branching factor of the pheromones.
+    ///
+    /// For each city, the paths whose pheromones are above
+    /// `min + lambda * (max - min)` are counted, where `min` and
+    /// `max` are the lowest and highest pheromones of the paths
+    /// leaving that city. Only the candidates of the city are looked
+    /// at when the candidate lists are built. A value close to `2`
+    /// means that the ants almost always walk the same tour.
+    ///
+    /// # Arguments
+    /// * `lambda` - Between `0.0` and `1.0`, usually `0.05`.
+    pub fn branching_factor(&self, lambda: f64) -> f64 {
+        let size = self.cities.len();
+
+        if size < 2 {
+            return 0.0
+        }
+
+        let mut total = 0usize;
+
+        for i in 0..size {
+            let candidates = self.candidates(i);
+            let paths: Vec<usize> = if candidates.is_empty() {
+                (0..size).filter(|&j| j != i).collect()
+            } else {
+                candidates.to_vec()
+            };
+
+            let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+
+            for &j in paths.iter() {
+                min = min.min(self.pheromones[i][j]);
+                max = max.max(self.pheromones[i][j]);
+            }
+
+            let threshold = min + lambda * (max - min);
+            total += paths.iter().filter(|&&j| self.pheromones[i][j] >= threshold).count();
+        }
+
+        total as f64 / size as f64
+    }
+
+    /// Returns the paths `(from, to)` walked by a tour. The path from
+    /// the last city back to the first one is only included when the
+    /// tours are closed (see `Map::mode`).
+    ///
+    /// # Arguments
+    /// * `tour` - The index of each city in the order they are visited.
+    pub fn paths<'a>(&self, tour: &'a [usize]) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let count = match self.mode {
+            TourMode::Closed => tour.len(),
+            _ => tour.len().saturating_sub(1),
+        };
+
+        (0..count).map(move |i| (tour[i], tour[(i + 1) % tour.len()]))
+    }
+
+    /// Returns the length of a tour, the path from the last city
+    /// back to the first one is included when the tours are closed.
+    ///
+    /// # Arguments
+    /// * `tour` - The index of each city in the order they are visited.
+    pub fn tour_length(&self, tour: &[usize]) -> f64 {
+        let mut length = 0.0;
+
+        for (from, to) in self.paths(tour) {
+            length += self.distance(from, to);
+        }
+
+        length
+    }
+
+    /// Returns the cost of a tour: its length, plus `lateness_penalty`
+    /// for each unit of lateness when some cities have a time window
+    /// (see `schedule::schedule`). The ants compare their tours by cost.
+    ///
+    /// # Arguments
+    /// * `tour` - The index of each city in the order they are visited.
+    pub fn tour_cost(&self, tour: &[usize]) -> f64 {
+        let length = self.tour_length(tour);
+
+        if !self.has_time_windows() {
+            return length
+        }
+
+        length + self.lateness_penalty * schedule::schedule(self, tour).lateness
+    }
+
+    /// Evaporate a part of the pheromones on every path
+    /// of the map. Must be called once per iteration.
+    ///
+    /// # Arguments
+    /// * `rho` - The evaporation rate, between `0.0` (nothing evaporate) and `1.0` (everything evaporate).
+    pub fn evaporate(&mut self, rho: f64) {
+        for paths in self.pheromones.iter_mut() {
+            for pheromone in paths.iter_mut() {
+                *pheromone *= 1.0 - rho;
+            }
+        }
+    }
+
+    /// Deposit `q / tour_cost` pheromones on each path of a
+    /// completed tour (see `Map::paths`). Cheaper tours deposit more.
+    /// On an asymmetric map, only the direction walked gets them.
+    ///
+    /// # Arguments
+    /// * `tour` - The index of each city in the order they are visited.
+    /// * `q` - The amount of pheromone an ant can deposit on its whole tour.
+    pub fn deposit(&mut self, tour: &[usize], q: f64) {
+        let length = self.tour_cost(tour);
+
+        if length <= 0.0 {
+            return
+        }
+
+        let amount = q / length;
+
+        for (from, to) in self.paths(tour) {
+            self.pheromones[from][to] += amount;
+
+            if self.is_symmetric() {
+                self.pheromones[to][from] += amount;
+            }
+        }
+    }
+
+    /// Returns the tour that starts at `from` and always takes the
+    /// path with the most pheromones from the current city to an
+    /// unvisited one (the shorter path wins a tie). A fixed path
+    /// starts at its `start` city and ends at its `end` city whatever
+    /// `from` is. For a tour that doesn't depend on the pheromones,
+    /// see `construction`.
+    ///
+    /// # Arguments
+    /// * `from` - The index of the first city.
+    pub fn best_path(&self, from: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.cities.len()];
+        let mut path = Vec::with_capacity(self.cities.len());
+
+        let (from, end) = match self.mode {
+            TourMode::Fixed { start, end } => (start, Some(end)),
+            _ => (from, None),
+        };
+
+        visited[from] = true;
+        path.push(from);
+
+        // The end of a fixed path is only visited last.
+        let count = match end {
+            Some(end) => {
+                visited[end] = true;
+                self.cities.len() - 1
+            }
+            None => self.cities.len(),
+        };
+
+        while path.len() < count {
+            let current = path[path.len() - 1];
+            let next = (0..self.cities.len())
+                .filter(|&i| !visited[i])
+                .max_by(|&a, &b| {
+                    let pheromones = self.pheromones[current][a].partial_cmp(&self.pheromones[current][b]);
+                    let distances = self.distance(current, b).partial_cmp(&self.distance(current, a));
+
+                    pheromones.unwrap_or(Ordering::Equal).then(distances.unwrap_or(Ordering::Equal))
+                })
+                .unwrap();
 
-            path.push(index);
-            already_visited.push(index);
+            visited[next] = true;
+            path.push(next);
         }
 
+        path.extend(end);
         path
     }
 }
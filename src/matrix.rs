@@ -0,0 +1,101 @@
+/// A square matrix, symmetric or not.
+///
+/// Only the lower triangle (diagonal included) of a symmetric
+/// matrix is stored, so `n * (n + 1) / 2` values are kept
+/// instead of `n * n`.
+#[derive(Clone, Debug)]
+pub struct SquareMatrix<T> {
+    /// The number of rows (and columns).
+    size: usize,
+
+    /// `true` when the value at row `i` and column `j`
+    /// is always the value at row `j` and column `i`.
+    symmetric: bool,
+
+    /// The values of the lower triangle, row after row,
+    /// or of every row when the matrix isn't symmetric.
+    values: Vec<T>,
+}
+
+impl<T: Copy> SquareMatrix<T> {
+    /// Create new symmetric matrix.
+    ///
+    /// # Arguments
+    /// * `size` - The number of rows (and columns).
+    /// * `value` - A function that returns the value at row `i` and column `j` (with `j <= i`).
+    pub fn symmetric<F: FnMut(usize, usize) -> T>(size: usize, mut value: F) -> SquareMatrix<T> {
+        let mut values = Vec::with_capacity(size * (size + 1) / 2);
+
+        for i in 0..size {
+            for j in 0..=i {
+                values.push(value(i, j));
+            }
+        }
+
+        SquareMatrix { size, symmetric: true, values }
+    }
+
+    /// Create new matrix where the value at row `i` and column `j`
+    /// may differ from the value at row `j` and column `i`.
+    ///
+    /// # Arguments
+    /// * `size` - The number of rows (and columns).
+    /// * `value` - A function that returns the value at row `i` and column `j`.
+    pub fn full<F: FnMut(usize, usize) -> T>(size: usize, mut value: F) -> SquareMatrix<T> {
+        let mut values = Vec::with_capacity(size * size);
+
+        for i in 0..size {
+            for j in 0..size {
+                values.push(value(i, j));
+            }
+        }
+
+        SquareMatrix { size, symmetric: false, values }
+    }
+
+    /// The number of rows (and columns).
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns `true` if the matrix was created with `symmetric`.
+    pub fn is_symmetric(&self) -> bool {
+        self.symmetric
+    }
+
+    /// Returns the value at row `i` and column `j`.
+    pub fn get(&self, i: usize, j: usize) -> T {
+        self.values[self.offset(i, j)]
+    }
+
+    /// Change the value at row `i` and column `j` (and at
+    /// row `j` and column `i` if the matrix is symmetric).
+    pub fn set(&mut self, i: usize, j: usize, value: T) {
+        let offset = self.offset(i, j);
+        self.values[offset] = value;
+    }
+
+    /// Returns a new matrix where each value is transformed by `f`.
+    ///
+    /// # Arguments
+    /// * `f` - The function applied on each value.
+    pub fn map<U: Copy, F: FnMut(T) -> U>(&self, f: F) -> SquareMatrix<U> {
+        SquareMatrix {
+            size: self.size,
+            symmetric: self.symmetric,
+            values: self.values.iter().copied().map(f).collect(),
+        }
+    }
+
+    /// Returns the position of a value in `values`.
+    fn offset(&self, i: usize, j: usize) -> usize {
+        debug_assert!(i < self.size && j < self.size);
+
+        if !self.symmetric {
+            return i * self.size + j
+        }
+
+        let (row, column) = if i >= j { (i, j) } else { (j, i) };
+        row * (row + 1) / 2 + column
+    }
+}
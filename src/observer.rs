@@ -0,0 +1,52 @@
+use crate::colony::Colony;
+use crate::stats::IterationStats;
+
+/// What an observer wants the colony to do next.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Control {
+    /// Keep iterating.
+    Continue,
+
+    /// Stop the colony, `Colony::run` returns
+    /// with `Termination::Cancelled`.
+    Stop,
+}
+
+/// Something that follows the progress of a colony (see
+/// `Colony::add_observer`). Every method does nothing by default.
+pub trait Observer {
+    /// Called before an iteration starts. When `Control::Stop`
+    /// is returned the iteration is not done.
+    ///
+    /// # Arguments
+    /// * `colony` - The colony.
+    fn iteration_started(&mut self, _colony: &Colony) -> Control {
+        Control::Continue
+    }
+
+    /// Called once an iteration is done.
+    ///
+    /// # Arguments
+    /// * `colony` - The colony.
+    /// * `stats` - The statistics of the iteration.
+    fn iteration_ended(&mut self, _colony: &Colony, _stats: &IterationStats) -> Control {
+        Control::Continue
+    }
+
+    /// Called when an ant found a tour shorter than the best one
+    /// so far, which is already stored in `colony.best_tour`.
+    ///
+    /// # Arguments
+    /// * `colony` - The colony.
+    fn new_best(&mut self, _colony: &Colony) -> Control {
+        Control::Continue
+    }
+
+    /// Called when the pheromone update reset the pheromones.
+    ///
+    /// # Arguments
+    /// * `colony` - The colony.
+    fn pheromones_reset(&mut self, _colony: &Colony) -> Control {
+        Control::Continue
+    }
+}
@@ -0,0 +1,223 @@
+use crate::local_search::{LocalSearch, LocalSearchScope};
+use crate::strategy::Strategy;
+
+use std::time::Duration;
+
+/// A parameters error.
+#[derive(Debug)]
+pub struct ParamsError {
+    /// The name of the invalid parameter.
+    pub kind: String,
+
+    /// The error description.
+    pub message: String,
+}
+
+impl ParamsError {
+    /// Create new parameters error.
+    ///
+    /// # Arguments
+    /// * `kind` - The name of the invalid parameter.
+    /// * `message` - The error description.
+    pub fn new(kind: &str, message: &str) -> ParamsError {
+        ParamsError {
+            kind: String::from(kind),
+            message: String::from(message),
+        }
+    }
+}
+
+/// The parameters used by the ants to explore a map.
+#[derive(Clone, Debug)]
+pub struct AcoParams {
+    /// How much the pheromones attract an ant to a city.
+    pub alpha: f64,
+
+    /// How much the distance to a city repels an ant.
+    pub beta: f64,
+
+    /// The part of the pheromones that evaporate at each
+    /// iteration, between `0.0` (exclusive) and `1.0`.
+    pub rho: f64,
+
+    /// The amount of pheromones an ant deposit on its whole tour.
+    pub q: f64,
+
+    /// The number of ants that explore the map at each iteration.
+    pub ant_count: usize,
+
+    /// The maximum number of iterations.
+    pub iterations: usize,
+
+    /// The colony stops once this time is spent. It is checked
+    /// between two iterations, so the last one may end a bit later.
+    pub time_limit: Option<Duration>,
+
+    /// The colony stops once a tour at least this short is
+    /// found (the length of the optimal tour when it is known).
+    pub target_length: Option<f64>,
+
+    /// The colony stops when the best tour isn't
+    /// improved for this many iterations.
+    pub stagnation_limit: Option<usize>,
+
+    /// The amount of pheromones on each path before the first
+    /// iteration. `0.0` means `1 / (n * L_nn)`, where `L_nn` is the
+    /// length of a nearest neighbour tour (see `construction`).
+    pub initial_pheromone: f64,
+
+    /// The probability that an ant goes to the most attractive city
+    /// instead of drawing one (`0.0` means the ants always draw).
+    pub q0: f64,
+
+    /// The seed of the random number generator. The same seed
+    /// on the same map always gives the same tours. When `None`
+    /// a random seed is used.
+    pub seed: Option<u64>,
+
+    /// The number of nearest cities an ant looks at when choosing
+    /// the next city. `0` means that every city is looked at.
+    pub candidates: usize,
+
+    /// How the tours walked by the ants are improved.
+    pub local_search: LocalSearch,
+
+    /// How the pheromones are updated after each iteration.
+    pub strategy: Strategy,
+
+    /// The number of threads used to build and improve the tours
+    /// of an iteration. `0` means one thread per available core.
+    /// The tours don't depend on it.
+    pub threads: usize,
+}
+
+impl Default for AcoParams {
+    fn default() -> Self {
+        AcoParams {
+            alpha: 1.1,
+            beta: 2.3,
+            rho: 0.5,
+            q: 1.0,
+            ant_count: 10,
+            iterations: 100,
+            time_limit: None,
+            target_length: None,
+            stagnation_limit: None,
+            initial_pheromone: 1.0,
+            q0: 0.0,
+            seed: None,
+            candidates: 20,
+            local_search: LocalSearch::default(),
+            strategy: Strategy::default(),
+            threads: 1,
+        }
+    }
+}
+
+impl AcoParams {
+    /// Returns the probability that an ant goes to the most attractive
+    /// city: `q0` of the Ant Colony System when it is the strategy,
+    /// `self.q0` otherwise.
+    pub fn exploitation(&self) -> f64 {
+        match &self.strategy {
+            Strategy::AntColonySystem(acs) => acs.q0,
+            _ => self.q0,
+        }
+    }
+
+    /// Returns the number of threads to use, resolving
+    /// `0` to the number of available cores.
+    pub fn thread_count(&self) -> usize {
+        match self.threads {
+            0 => std::thread::available_parallelism().map_or(1, |count| count.get()),
+            threads => threads,
+        }
+    }
+
+    /// Check that every parameter is in its valid range.
+    pub fn validate(&self) -> Result<(), ParamsError> {
+        if !(self.alpha >= 0.0 && self.alpha.is_finite()) {
+            return Err(ParamsError::new("alpha", "alpha must be a finite positive number"));
+        }
+
+        if !(self.beta >= 0.0 && self.beta.is_finite()) {
+            return Err(ParamsError::new("beta", "beta must be a finite positive number"));
+        }
+
+        if !(self.rho > 0.0 && self.rho <= 1.0) {
+            return Err(ParamsError::new("rho", "rho must be in the range ]0, 1]"));
+        }
+
+        if !(self.q > 0.0 && self.q.is_finite()) {
+            return Err(ParamsError::new("q", "q must be a finite number greater than 0"));
+        }
+
+        if self.ant_count == 0 {
+            return Err(ParamsError::new("ant_count", "at least one ant is needed"));
+        }
+
+        if self.iterations == 0 {
+            return Err(ParamsError::new("iterations", "at least one iteration is needed"));
+        }
+
+        if self.target_length.is_some_and(f64::is_nan) {
+            return Err(ParamsError::new("target_length", "target_length must be a number"));
+        }
+
+        if self.stagnation_limit == Some(0) {
+            return Err(ParamsError::new("stagnation_limit", "stagnation_limit must be at least 1"));
+        }
+
+        if !(self.initial_pheromone >= 0.0 && self.initial_pheromone.is_finite()) {
+            return Err(ParamsError::new(
+                "initial_pheromone",
+                "initial_pheromone must be a finite positive number",
+            ));
+        }
+
+        if !(0.0..=1.0).contains(&self.q0) {
+            return Err(ParamsError::new("q0", "q0 must be in the range [0, 1]"));
+        }
+
+        if self.local_search.scope != LocalSearchScope::None && self.local_search.neighbours == 0 {
+            return Err(ParamsError::new(
+                "local_search",
+                "the local search needs at least one neighbour per city",
+            ));
+        }
+
+        if let Strategy::Elitist(elitist) = &self.strategy {
+            if !(elitist.weight >= 0.0 && elitist.weight.is_finite()) {
+                return Err(ParamsError::new("weight", "weight must be a finite positive number"));
+            }
+        }
+
+        if let Strategy::RankBased(rank_based) = &self.strategy {
+            if rank_based.width == 0 {
+                return Err(ParamsError::new("width", "width must be at least 1"));
+            }
+        }
+
+        if let Strategy::MaxMin(max_min) = &self.strategy {
+            if !(max_min.p_best > 0.0 && max_min.p_best < 1.0) {
+                return Err(ParamsError::new("p_best", "p_best must be in the range ]0, 1["));
+            }
+
+            if !(0.0..=1.0).contains(&max_min.lambda) {
+                return Err(ParamsError::new("lambda", "lambda must be in the range [0, 1]"));
+            }
+        }
+
+        if let Strategy::AntColonySystem(acs) = &self.strategy {
+            if !(0.0..=1.0).contains(&acs.q0) {
+                return Err(ParamsError::new("q0", "q0 must be in the range [0, 1]"));
+            }
+
+            if !(acs.xi > 0.0 && acs.xi <= 1.0) {
+                return Err(ParamsError::new("xi", "xi must be in the range ]0, 1]"));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use crate::city::City;
+use crate::map::Map;
+
+/// A vehicle that arrives less than this after the
+/// end of a time window isn't considered late.
+const EPSILON: f64 = 1e-9;
+
+/// When the service of a city may start.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimeWindow {
+    /// A vehicle that arrives before this time waits.
+    pub earliest: f64,
+
+    /// A vehicle that arrives after this time is late.
+    pub latest: f64,
+}
+
+impl TimeWindow {
+    /// Create new time window.
+    ///
+    /// # Arguments
+    /// * `earliest` - A vehicle that arrives before this time waits.
+    /// * `latest` - A vehicle that arrives after this time is late.
+    pub fn new(earliest: f64, latest: f64) -> TimeWindow {
+        TimeWindow { earliest, latest }
+    }
+}
+
+/// A city reached by a tour.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Visit {
+    /// The index of the city.
+    pub city: usize,
+
+    /// When the vehicle arrives.
+    pub arrival: f64,
+
+    /// When the service starts, once the time window is open.
+    pub start: f64,
+
+    /// How long the vehicle waits for the time window to open.
+    pub waiting: f64,
+
+    /// How late the service starts after the time window closed.
+    pub lateness: f64,
+}
+
+/// When each city of a tour is reached. The travel time between two
+/// cities is their distance, and a vehicle spends the `service_time`
+/// of a city there before leaving.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schedule {
+    /// The cities in the order they are reached. The first city of a
+    /// closed tour is reached again at the end, and the depot of a
+    /// vehicle routing problem at the end of each route.
+    pub visits: Vec<Visit>,
+
+    /// The total time spent waiting.
+    pub waiting: f64,
+
+    /// The total lateness.
+    pub lateness: f64,
+}
+
+impl Schedule {
+    /// Returns `true` if no city is served late.
+    pub fn is_feasible(&self) -> bool {
+        self.lateness <= EPSILON
+    }
+
+    /// Returns the cities served late.
+    pub fn late_cities(&self) -> Vec<usize> {
+        self.visits
+            .iter()
+            .filter(|visit| visit.lateness > EPSILON)
+            .map(|visit| visit.city)
+            .collect()
+    }
+}
+
+/// Returns the schedule of a tour. The tour starts at `0` in its first
+/// city, or when the time window of that city opens, and on a vehicle
+/// routing problem each route starts from the depot the same way. Only
+/// the depot must be reached in time at the end of a route, a closed
+/// tour without a fleet ends whenever it gets back to its first city.
+///
+/// # Arguments
+/// * `map` - A map.
+/// * `tour` - The index of each city in the order they are visited.
+pub fn schedule(map: &Map, tour: &[usize]) -> Schedule {
+    let mut visits = Vec::with_capacity(tour.len() + 1);
+    let (mut waiting, mut lateness) = (0.0, 0.0);
+
+    let first = match tour.first() {
+        Some(&first) => first,
+        None => return Schedule { visits, waiting, lateness },
+    };
+
+    let start = service_start(&map.cities[first], 0.0);
+    visits.push(Visit { city: first, arrival: start, start, waiting: 0.0, lateness: 0.0 });
+
+    let mut time = departure(&map.cities[first]);
+    let closed_count = map.paths(tour).count();
+
+    for (i, (from, to)) in map.paths(tour).enumerate() {
+        let arrival = time + map.distance(from, to);
+        let city = &map.cities[to];
+        let back = i + 1 == closed_count && to == first && tour.len() > 1;
+        let depot = map.fleet.is_some_and(|fleet| fleet.depot == to);
+
+        // The vehicle ends its tour (or its route) there...
+        if back || depot {
+            let late = match city.time_window {
+                Some(window) if depot => (arrival - window.latest).max(0.0),
+                _ => 0.0,
+            };
+            visits.push(Visit { city: to, arrival, start: arrival, waiting: 0.0, lateness: late });
+            lateness += late;
+
+            // ... and a new vehicle leaves the depot.
+            time = departure(city);
+            continue
+        }
+
+        let start = service_start(city, arrival);
+        let late = city.time_window.map_or(0.0, |window| (start - window.latest).max(0.0));
+
+        visits.push(Visit { city: to, arrival, start, waiting: start - arrival, lateness: late });
+        waiting += start - arrival;
+        lateness += late;
+        time = start + city.service_time;
+    }
+
+    Schedule { visits, waiting, lateness }
+}
+
+/// Returns when the service of a city starts if it's reached at
+/// `arrival`: not before its time window opens.
+///
+/// # Arguments
+/// * `city` - The city.
+/// * `arrival` - When the vehicle arrives.
+pub fn service_start(city: &City, arrival: f64) -> f64 {
+    match city.time_window {
+        Some(window) => arrival.max(window.earliest),
+        None => arrival,
+    }
+}
+
+/// Returns `true` if a vehicle that arrives at `arrival` is late.
+///
+/// # Arguments
+/// * `city` - The city.
+/// * `arrival` - When the vehicle arrives.
+pub fn is_late(city: &City, arrival: f64) -> bool {
+    city.time_window.is_some_and(|window| arrival > window.latest + EPSILON)
+}
+
+/// Returns when a vehicle that starts its tour (or
+/// its route) at a city leaves it, once served.
+///
+/// # Arguments
+/// * `city` - The first city of the tour.
+pub fn departure(city: &City) -> f64 {
+    service_start(city, 0.0) + city.service_time
+}
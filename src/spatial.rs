@@ -0,0 +1,156 @@
+use cgmath::Vector2;
+
+/// A grid that splits the plane in square cells, so the
+/// nearest points of a position can be found without
+/// looking at every point.
+pub struct Grid {
+    /// The position of the corner of the first cell.
+    origin: Vector2<f64>,
+
+    /// The width (and height) of a cell.
+    cell_size: f64,
+
+    /// The number of cells on the x axis.
+    columns: usize,
+
+    /// The number of cells on the y axis.
+    rows: usize,
+
+    /// The index of the points in each cell, row after row.
+    cells: Vec<Vec<usize>>,
+
+    /// The position of each point.
+    points: Vec<Vector2<f64>>,
+}
+
+impl Grid {
+    /// Create new grid with about two points per cell.
+    ///
+    /// # Arguments
+    /// * `points` - The position of each point.
+    pub fn new(points: &[Vector2<f32>]) -> Grid {
+        let points: Vec<Vector2<f64>> = points.iter().map(|p| Vector2::new(p.x as f64, p.y as f64)).collect();
+
+        let mut min = Vector2::new(f64::INFINITY, f64::INFINITY);
+        let mut max = Vector2::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+
+        for point in points.iter() {
+            min = Vector2::new(min.x.min(point.x), min.y.min(point.y));
+            max = Vector2::new(max.x.max(point.x), max.y.max(point.y));
+        }
+
+        if points.is_empty() {
+            min = Vector2::new(0.0, 0.0);
+            max = min;
+        }
+
+        let (width, height) = (max.x - min.x, max.y - min.y);
+        let area = (width * height).max(width * width).max(height * height);
+        let cell_size = if area > 0.0 {
+            (2.0 * area / points.len() as f64).sqrt()
+        } else {
+            1.0
+        };
+
+        let columns = (width / cell_size) as usize + 1;
+        let rows = (height / cell_size) as usize + 1;
+        let mut cells = vec![Vec::new(); columns * rows];
+
+        let mut grid = Grid {
+            origin: min,
+            cell_size,
+            columns,
+            rows,
+            cells: Vec::new(),
+            points,
+        };
+
+        for (i, point) in grid.points.iter().enumerate() {
+            let (column, row) = grid.cell(*point);
+            cells[row * columns + column].push(i);
+        }
+
+        grid.cells = cells;
+        grid
+    }
+
+    /// Returns the `count` nearest points of a point,
+    /// from the nearest to the farthest.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the point.
+    /// * `count` - The number of points to find.
+    pub fn nearest(&self, index: usize, count: usize) -> Vec<usize> {
+        let point = self.points[index];
+        let (column, row) = self.cell(point);
+        let count = count.min(self.points.len().saturating_sub(1));
+
+        if count == 0 {
+            return Vec::new()
+        }
+
+        // The squared distance and index of the nearest points found so far.
+        let mut nearest: Vec<(f64, usize)> = Vec::with_capacity(count + 1);
+        let mut ring = 0;
+
+        loop {
+            for (c, r) in self.ring_cells(column, row, ring) {
+                for &other in self.cells[r * self.columns + c].iter() {
+                    if other == index {
+                        continue
+                    }
+
+                    let delta = self.points[other] - point;
+                    let distance = delta.x * delta.x + delta.y * delta.y;
+
+                    if nearest.len() == count && distance >= nearest[count - 1].0 {
+                        continue
+                    }
+
+                    let at = nearest.partition_point(|&(d, _)| d <= distance);
+                    nearest.insert(at, (distance, other));
+                    nearest.truncate(count);
+                }
+            }
+
+            // Points in the next rings are at least `ring * cell_size` away.
+            let bound = ring as f64 * self.cell_size;
+            let farthest = nearest.last().map_or(0.0, |&(distance, _)| distance);
+            let covered = ring >= self.columns.max(self.rows);
+
+            if covered || (nearest.len() == count && farthest <= bound * bound) {
+                break
+            }
+
+            ring += 1;
+        }
+
+        nearest.into_iter().map(|(_, i)| i).collect()
+    }
+
+    /// Returns the cell (column and row) of a position.
+    fn cell(&self, point: Vector2<f64>) -> (usize, usize) {
+        let column = ((point.x - self.origin.x) / self.cell_size) as usize;
+        let row = ((point.y - self.origin.y) / self.cell_size) as usize;
+
+        (column.min(self.columns - 1), row.min(self.rows - 1))
+    }
+
+    /// Returns the cells at `ring` cells from a cell.
+    fn ring_cells(&self, column: usize, row: usize, ring: usize) -> Vec<(usize, usize)> {
+        let (column, row, ring) = (column as isize, row as isize, ring as isize);
+        let mut cells = Vec::new();
+
+        for r in row - ring..=row + ring {
+            for c in column - ring..=column + ring {
+                let on_ring = (r - row).abs() == ring || (c - column).abs() == ring;
+
+                if on_ring && r >= 0 && c >= 0 && (r as usize) < self.rows && (c as usize) < self.columns {
+                    cells.push((c as usize, r as usize));
+                }
+            }
+        }
+
+        cells
+    }
+}
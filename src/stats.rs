@@ -0,0 +1,128 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// The `lambda` used to compute the branching factor of the statistics.
+pub const BRANCHING_LAMBDA: f64 = 0.05;
+
+/// What happened during one iteration.
+#[derive(Clone, Debug)]
+pub struct IterationStats {
+    /// The index of the iteration, starting at `0`.
+    pub iteration: usize,
+
+    /// The length of the shortest tour of the iteration.
+    pub best_length: f64,
+
+    /// The average length of the tours of the iteration.
+    pub mean_length: f64,
+
+    /// The length of the longest tour of the iteration.
+    pub worst_length: f64,
+
+    /// The length of the shortest tour found so far.
+    pub global_best: f64,
+
+    /// The branching factor of the pheromones after the update
+    /// (see `Map::branching_factor`), with `BRANCHING_LAMBDA`.
+    pub branching_factor: f64,
+
+    /// `true` if the pheromones were reset by the update.
+    pub reset: bool,
+
+    /// The wall-clock time spent since the first iteration started,
+    /// until the end of this one (see `Colony::elapsed`).
+    pub elapsed: Duration,
+}
+
+/// The statistics of every iteration, in order.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub iterations: Vec<IterationStats>,
+}
+
+impl History {
+    /// Create new empty history.
+    pub fn new() -> History {
+        History { iterations: Vec::new() }
+    }
+
+    /// Returns the history as CSV, with a header line
+    /// and one line per iteration. The elapsed time is in seconds.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "iteration,best_length,mean_length,worst_length,global_best,branching_factor,reset,elapsed\n",
+        );
+
+        for stats in self.iterations.iter() {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                stats.iteration,
+                stats.best_length,
+                stats.mean_length,
+                stats.worst_length,
+                stats.global_best,
+                stats.branching_factor,
+                stats.reset,
+                stats.elapsed.as_secs_f64(),
+            ));
+        }
+
+        csv
+    }
+
+    /// Returns the history as a JSON array with one object per
+    /// iteration. The elapsed time is in seconds and the numbers
+    /// that aren't finite are written as `null`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+
+        for (i, stats) in self.iterations.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+
+            json.push_str(&format!(
+                "\n  {{\"iteration\": {}, \"best_length\": {}, \"mean_length\": {}, \"worst_length\": {}, \
+                 \"global_best\": {}, \"branching_factor\": {}, \"reset\": {}, \"elapsed\": {}}}",
+                stats.iteration,
+                json_number(stats.best_length),
+                json_number(stats.mean_length),
+                json_number(stats.worst_length),
+                json_number(stats.global_best),
+                json_number(stats.branching_factor),
+                stats.reset,
+                json_number(stats.elapsed.as_secs_f64()),
+            ));
+        }
+
+        json.push_str("\n]\n");
+        json
+    }
+
+    /// Write the history to a CSV file (see `History::to_csv`).
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file.
+    pub fn save_csv<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_csv())
+    }
+
+    /// Write the history to a JSON file (see `History::to_json`).
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file.
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+}
+
+/// Returns a number as JSON, `null` when it isn't finite.
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        format!("{}", value)
+    } else {
+        String::from("null")
+    }
+}
@@ -0,0 +1,438 @@
+use crate::ant::Ant;
+use crate::construction;
+use crate::map::Map;
+use crate::params::AcoParams;
+
+/// What a pheromone update knows about the iteration that just ended.
+pub struct Iteration<'a> {
+    /// The ants, with the tour they walked during the iteration.
+    pub ants: &'a [Ant],
+
+    /// The index of the ant that walked the shortest tour of the iteration.
+    pub iteration_best: usize,
+
+    /// The shortest tour walked by an ant so far.
+    pub best_tour: &'a [usize],
+
+    /// The length of the shortest tour so far.
+    pub best_length: f64,
+
+    /// The number of iterations done before this one.
+    pub iteration: usize,
+
+    /// The iteration where the best tour was last improved.
+    pub last_improvement: usize,
+}
+
+/// A rule that updates the pheromones of a map. The colony uses
+/// the one of `params.strategy`, or any other given to
+/// `Colony::set_pheromone_update`.
+pub trait PheromoneUpdate {
+    /// Set the pheromones of the map before the first iteration.
+    ///
+    /// # Arguments
+    /// * `map` - The map explored by the ants.
+    /// * `params` - The colony parameters.
+    fn initialize(&mut self, map: &mut Map, params: &AcoParams);
+
+    /// Called each time an ant completed its tour, before the
+    /// next ant starts its own. Does nothing by default.
+    ///
+    /// # Arguments
+    /// * `map` - The map explored by the ants.
+    /// * `ant` - The ant that completed its tour.
+    /// * `params` - The colony parameters.
+    fn local_update(&mut self, _map: &mut Map, _ant: &Ant, _params: &AcoParams) {}
+
+    /// Returns `true` if `local_update` modifies the pheromones. The
+    /// ants then build their tours one after the other, even when
+    /// several threads are used.
+    fn updates_locally(&self) -> bool {
+        false
+    }
+
+    /// Update the pheromones once every ant completed its tour.
+    /// Returns `true` if the pheromones were reset.
+    ///
+    /// # Arguments
+    /// * `map` - The map explored by the ants.
+    /// * `iteration` - The iteration that just ended.
+    /// * `params` - The colony parameters.
+    fn update(&mut self, map: &mut Map, iteration: &Iteration, params: &AcoParams) -> bool;
+
+    /// Returns what the update needs to continue where it stopped,
+    /// saved in the checkpoints. Nothing by default.
+    fn state(&self) -> Vec<f64> {
+        Vec::new()
+    }
+
+    /// Restore the state returned by `state`. It is called
+    /// after `initialize` when a checkpoint is loaded.
+    ///
+    /// # Arguments
+    /// * `state` - The saved state.
+    fn restore(&mut self, _state: &[f64]) {}
+}
+
+/// The pheromone updates that come with the crate.
+#[derive(Clone, Debug, Default)]
+pub enum Strategy {
+    /// Every ant deposits `q / length` on its tour.
+    #[default]
+    AntSystem,
+
+    /// Every ant deposits, and the best tour so
+    /// far gets an extra deposit.
+    Elitist(ElitistParams),
+
+    /// Only the best ants of the iteration and the best tour so
+    /// far deposit, the better the rank the more they deposit.
+    RankBased(RankBasedParams),
+
+    /// Only the iteration best (or best so far) tour deposits, and
+    /// the pheromones are kept between two computed limits.
+    MaxMin(MaxMinParams),
+
+    /// The ants mostly go to the most attractive city and remove
+    /// some pheromones from the paths they walk, then only the best
+    /// tour so far deposits.
+    AntColonySystem(AcsParams),
+}
+
+impl Strategy {
+    /// Returns the pheromone update of the strategy.
+    pub fn build(&self) -> Box<dyn PheromoneUpdate> {
+        match self {
+            Strategy::AntSystem => Box::new(AntSystem),
+            Strategy::Elitist(params) => Box::new(ElitistAntSystem { params: params.clone() }),
+            Strategy::RankBased(params) => Box::new(RankBasedAntSystem { params: params.clone() }),
+            Strategy::MaxMin(params) => Box::new(MaxMinAntSystem::new(params.clone())),
+            Strategy::AntColonySystem(params) => Box::new(AntColonySystem::new(params.clone())),
+        }
+    }
+}
+
+/// The parameters of the elitist Ant System.
+#[derive(Clone, Debug)]
+pub struct ElitistParams {
+    /// The best tour so far gets an extra `weight * q / length`
+    /// at each iteration. The number of cities is a usual value.
+    pub weight: f64,
+}
+
+impl Default for ElitistParams {
+    fn default() -> Self {
+        ElitistParams { weight: 5.0 }
+    }
+}
+
+/// The parameters of the rank-based Ant System.
+#[derive(Clone, Debug)]
+pub struct RankBasedParams {
+    /// The best tour so far deposits `width * q / length` and the
+    /// ant of rank `r` (from `1` to `width - 1`) of the iteration
+    /// deposits `(width - r) * q / length`.
+    pub width: usize,
+}
+
+impl Default for RankBasedParams {
+    fn default() -> Self {
+        RankBasedParams { width: 6 }
+    }
+}
+
+/// The parameters of the MAX-MIN Ant System.
+#[derive(Clone, Debug)]
+pub struct MaxMinParams {
+    /// Every `global_best_every` iterations the best tour so far
+    /// deposits instead of the best tour of the iteration
+    /// (`0` means never and `1` means always).
+    pub global_best_every: usize,
+
+    /// The probability to build the best tour again once
+    /// the pheromones converged. It sets the lower limit.
+    pub p_best: f64,
+
+    /// The `lambda` used to compute the branching factor
+    /// (see `Map::branching_factor`).
+    pub lambda: f64,
+
+    /// The pheromones are reset to the upper limit when
+    /// the branching factor drops below this value.
+    pub branching_threshold: f64,
+
+    /// The pheromones are also reset when the best tour isn't
+    /// improved for this many iterations (`0` means never).
+    pub stagnation_iterations: usize,
+
+    /// The branching factor is only checked when the best tour wasn't
+    /// improved (nor the pheromones reset) for this many iterations,
+    /// so the ants have time to follow the new pheromones.
+    pub reset_delay: usize,
+}
+
+impl Default for MaxMinParams {
+    fn default() -> Self {
+        MaxMinParams {
+            global_best_every: 10,
+            p_best: 0.05,
+            lambda: 0.05,
+            branching_threshold: 2.05,
+            stagnation_iterations: 0,
+            reset_delay: 50,
+        }
+    }
+}
+
+impl MaxMinParams {
+    /// Returns the pheromone limits `(tau_min, tau_max)`.
+    ///
+    /// # Arguments
+    /// * `best_length` - The length of the best tour so far.
+    /// * `city_count` - The number of cities of the map.
+    /// * `rho` - The evaporation rate.
+    /// * `q` - The amount of pheromones an ant deposit on its whole tour.
+    pub fn limits(&self, best_length: f64, city_count: usize, rho: f64, q: f64) -> (f64, f64) {
+        let tau_max = q / (rho * best_length);
+
+        // The probability to choose the best path at each step...
+        let p_dec = self.p_best.powf(1.0 / city_count as f64);
+        // ... among this number of paths on average.
+        let average = city_count as f64 / 2.0;
+
+        if average <= 1.0 {
+            return (tau_max, tau_max)
+        }
+
+        let tau_min = tau_max * (1.0 - p_dec) / ((average - 1.0) * p_dec);
+
+        (tau_min.min(tau_max), tau_max)
+    }
+}
+
+/// The parameters of the Ant Colony System.
+#[derive(Clone, Debug)]
+pub struct AcsParams {
+    /// The probability that an ant goes to the most attractive
+    /// city instead of drawing one. It replaces `AcoParams::q0`.
+    pub q0: f64,
+
+    /// How much the pheromones of a path get back to their initial
+    /// value (`tau0`) when an ant walks it (local update).
+    pub xi: f64,
+}
+
+impl Default for AcsParams {
+    fn default() -> Self {
+        AcsParams { q0: 0.9, xi: 0.1 }
+    }
+}
+
+/// The original Ant System: every ant deposits `q / length` on its tour.
+pub struct AntSystem;
+
+impl PheromoneUpdate for AntSystem {
+    /// The pheromones start at `params.initial_pheromone`,
+    /// or at `1 / (n * L_nn)` when it is `0`.
+    fn initialize(&mut self, map: &mut Map, params: &AcoParams) {
+        let value = match params.initial_pheromone {
+            value if value > 0.0 => value,
+            _ => construction::nearest_neighbour_pheromone(map),
+        };
+
+        map.reset_pheromones(value);
+    }
+
+    fn update(&mut self, map: &mut Map, iteration: &Iteration, params: &AcoParams) -> bool {
+        map.evaporate(params.rho);
+
+        for ant in iteration.ants.iter() {
+            map.deposit(&ant.tour, params.q);
+        }
+
+        false
+    }
+}
+
+/// The elitist Ant System: the Ant System plus an extra
+/// deposit of `weight * q / length` on the best tour so far.
+pub struct ElitistAntSystem {
+    /// The elitist Ant System parameters.
+    pub params: ElitistParams,
+}
+
+impl PheromoneUpdate for ElitistAntSystem {
+    fn initialize(&mut self, map: &mut Map, params: &AcoParams) {
+        AntSystem.initialize(map, params);
+    }
+
+    fn update(&mut self, map: &mut Map, iteration: &Iteration, params: &AcoParams) -> bool {
+        AntSystem.update(map, iteration, params);
+        map.deposit(iteration.best_tour, self.params.weight * params.q);
+
+        false
+    }
+}
+
+/// The rank-based Ant System: only the `width - 1` best ants of the
+/// iteration and the best tour so far deposit, weighted by their rank.
+pub struct RankBasedAntSystem {
+    /// The rank-based Ant System parameters.
+    pub params: RankBasedParams,
+}
+
+impl PheromoneUpdate for RankBasedAntSystem {
+    fn initialize(&mut self, map: &mut Map, params: &AcoParams) {
+        AntSystem.initialize(map, params);
+    }
+
+    fn update(&mut self, map: &mut Map, iteration: &Iteration, params: &AcoParams) -> bool {
+        let width = self.params.width;
+        let mut ranking: Vec<&Ant> = iteration.ants.iter().collect();
+        ranking.sort_by(|a, b| a.length.partial_cmp(&b.length).unwrap_or(std::cmp::Ordering::Equal));
+
+        map.evaporate(params.rho);
+
+        for (i, ant) in ranking.iter().take(width.saturating_sub(1)).enumerate() {
+            // The ant of rank `i + 1`...
+            map.deposit(&ant.tour, (width - i - 1) as f64 * params.q);
+        }
+
+        map.deposit(iteration.best_tour, width as f64 * params.q);
+
+        false
+    }
+}
+
+/// The MAX-MIN Ant System.
+pub struct MaxMinAntSystem {
+    /// The MAX-MIN Ant System parameters.
+    pub params: MaxMinParams,
+
+    /// The lower pheromone limit.
+    pub tau_min: f64,
+
+    /// The upper pheromone limit.
+    pub tau_max: f64,
+
+    /// The iteration where the pheromones were last reset.
+    last_reset: usize,
+}
+
+impl MaxMinAntSystem {
+    /// Create new MAX-MIN Ant System.
+    ///
+    /// # Arguments
+    /// * `params` - The MAX-MIN Ant System parameters.
+    pub fn new(params: MaxMinParams) -> MaxMinAntSystem {
+        MaxMinAntSystem {
+            params,
+            tau_min: 0.0,
+            tau_max: f64::INFINITY,
+            last_reset: 0,
+        }
+    }
+}
+
+impl PheromoneUpdate for MaxMinAntSystem {
+    /// The pheromones start at the upper limit, estimated
+    /// with the length of a nearest neighbour tour.
+    fn initialize(&mut self, map: &mut Map, params: &AcoParams) {
+        let length = map.tour_length(&construction::nearest_neighbour(map, 0));
+        let (tau_min, tau_max) = self.params.limits(length, map.cities.len(), params.rho, params.q);
+
+        self.tau_min = tau_min;
+        self.tau_max = tau_max;
+        self.last_reset = 0;
+        map.reset_pheromones(tau_max);
+    }
+
+    fn update(&mut self, map: &mut Map, iteration: &Iteration, params: &AcoParams) -> bool {
+        map.evaporate(params.rho);
+
+        let every = self.params.global_best_every;
+        let tour = if (iteration.iteration + 1).checked_rem(every) == Some(0) {
+            iteration.best_tour
+        } else {
+            &iteration.ants[iteration.iteration_best].tour
+        };
+
+        map.deposit(tour, params.q);
+
+        let (tau_min, tau_max) = self.params.limits(iteration.best_length, map.cities.len(), params.rho, params.q);
+        self.tau_min = tau_min;
+        self.tau_max = tau_max;
+        map.clamp_pheromones(tau_min, tau_max);
+
+        // Start again from the upper limit when the
+        // ants keep walking the same tours...
+        let since = iteration.iteration - iteration.last_improvement.max(self.last_reset);
+        let stagnating = self.params.stagnation_iterations > 0 && since >= self.params.stagnation_iterations;
+        let converged = since >= self.params.reset_delay
+            && map.branching_factor(self.params.lambda) < self.params.branching_threshold;
+
+        if stagnating || converged {
+            map.reset_pheromones(tau_max);
+            self.last_reset = iteration.iteration;
+            return true
+        }
+
+        false
+    }
+
+    fn state(&self) -> Vec<f64> {
+        vec![self.tau_min, self.tau_max, self.last_reset as f64]
+    }
+
+    fn restore(&mut self, state: &[f64]) {
+        if let [tau_min, tau_max, last_reset] = *state {
+            self.tau_min = tau_min;
+            self.tau_max = tau_max;
+            self.last_reset = last_reset as usize;
+        }
+    }
+}
+
+/// The Ant Colony System.
+pub struct AntColonySystem {
+    /// The Ant Colony System parameters.
+    pub params: AcsParams,
+
+    /// The initial pheromones of the paths, `1 / (n * L_nn)`.
+    pub tau0: f64,
+}
+
+impl AntColonySystem {
+    /// Create new Ant Colony System.
+    ///
+    /// # Arguments
+    /// * `params` - The Ant Colony System parameters.
+    pub fn new(params: AcsParams) -> AntColonySystem {
+        AntColonySystem { params, tau0: 0.0 }
+    }
+}
+
+impl PheromoneUpdate for AntColonySystem {
+    fn initialize(&mut self, map: &mut Map, _params: &AcoParams) {
+        self.tau0 = construction::nearest_neighbour_pheromone(map);
+        map.reset_pheromones(self.tau0);
+    }
+
+    /// The paths walked by the ant get closer to `tau0`. An ant never
+    /// walks a path twice, so it's the same than updating each path
+    /// as soon as it is walked.
+    fn local_update(&mut self, map: &mut Map, ant: &Ant, _params: &AcoParams) {
+        map.blend_pheromones(&ant.tour, self.params.xi, self.tau0);
+    }
+
+    fn updates_locally(&self) -> bool {
+        true
+    }
+
+    /// Only the paths of the best tour so far evaporate and get pheromones.
+    fn update(&mut self, map: &mut Map, iteration: &Iteration, params: &AcoParams) -> bool {
+        map.blend_pheromones(iteration.best_tour, params.rho, params.q / iteration.best_length);
+
+        false
+    }
+}
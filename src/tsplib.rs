@@ -0,0 +1,517 @@
+use crate::city::City;
+use crate::map::Map;
+use crate::matrix::SquareMatrix;
+use crate::vrp::Fleet;
+
+use cgmath::Vector2;
+
+/// A TSPLIB error.
+#[derive(Debug)]
+pub struct TsplibError {
+    /// Kind of error.
+    pub kind: String,
+
+    /// The error description.
+    pub message: String,
+}
+
+impl TsplibError {
+    /// Create new TSPLIB error.
+    ///
+    /// # Arguments
+    /// * `kind` - The kind of error.
+    /// * `message` - The error description.
+    pub fn new(kind: &str, message: &str) -> TsplibError {
+        TsplibError {
+            kind: String::from(kind),
+            message: String::from(message),
+        }
+    }
+}
+
+impl From<std::io::Error> for TsplibError {
+    /// Convert an `std::io::Error` to a `TsplibError`
+    ///
+    /// # Arguments
+    /// * `error` - The error to convert.
+    fn from(error: std::io::Error) -> Self {
+        TsplibError {
+            kind: String::from("io"),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// A problem instance read from a TSPLIB file.
+pub struct Instance {
+    /// The name of the instance.
+    pub name: String,
+
+    /// The comments of the instance.
+    pub comment: String,
+
+    /// The map built from the instance. The distances
+    /// follow the TSPLIB rounding rules.
+    pub map: Map,
+}
+
+/// Load a TSPLIB problem file (`.tsp`, `.atsp` or `.vrp`).
+///
+/// # Arguments
+/// * `path` - The path of the file.
+pub fn load_tsp(path: &str) -> Result<Instance, TsplibError> {
+    parse_tsp(&std::fs::read_to_string(path)?)
+}
+
+/// Parse the content of a TSPLIB problem file.
+///
+/// The supported edge weight types are `EUC_2D`, `CEIL_2D`, `ATT`,
+/// `GEO` and `EXPLICIT` (with the `FULL_MATRIX`, `UPPER_ROW`, `LOWER_ROW`,
+/// `UPPER_DIAG_ROW` and `LOWER_DIAG_ROW` formats). An `ATSP` problem
+/// must give its distances as an `EXPLICIT` `FULL_MATRIX`, where row `i`
+/// holds the distances from city `i`, and gives an asymmetric map. A
+/// `CVRP` problem (as written by CVRPLIB) needs a `CAPACITY`, a
+/// `DEMAND_SECTION` and a `DEPOT_SECTION` with a single depot, and
+/// gives a map with a fleet.
+///
+/// # Arguments
+/// * `content` - The content of the file.
+pub fn parse_tsp(content: &str) -> Result<Instance, TsplibError> {
+    let mut name = String::new();
+    let mut comment = String::new();
+    let mut dimension = None;
+    let mut edge_weight_type = None;
+    let mut edge_weight_format = None;
+    let mut coordinates = None;
+    let mut display_coordinates = None;
+    let mut weights = None;
+    let mut asymmetric = false;
+    let mut vehicle_routing = false;
+    let mut capacity = None;
+    let mut demands = None;
+    let mut depot = 0;
+
+    let mut lines = content.lines().enumerate();
+
+    while let Some((number, line)) = lines.next() {
+        let (keyword, value) = split_keyword(line);
+
+        match keyword {
+            "" => continue,
+            "NAME" => name = String::from(value),
+            "COMMENT" => {
+                if !comment.is_empty() {
+                    comment.push('\n');
+                }
+                comment.push_str(value);
+            }
+            "TYPE" => match value {
+                "TSP" => {}
+                "ATSP" => asymmetric = true,
+                "CVRP" => vehicle_routing = true,
+                _ => return Err(unsupported(number, "TYPE", value)),
+            },
+            "CAPACITY" => capacity = Some(parse_number::<f64>(number, value)?),
+            "DIMENSION" => dimension = Some(parse_number::<usize>(number, value)?),
+            "EDGE_WEIGHT_TYPE" => edge_weight_type = Some(String::from(value)),
+            "EDGE_WEIGHT_FORMAT" => edge_weight_format = Some(String::from(value)),
+            "NODE_COORD_TYPE" | "DISPLAY_DATA_TYPE" => {}
+            "NODE_COORD_SECTION" => {
+                let dimension = require_dimension(number, dimension)?;
+                coordinates = Some(read_coordinates(&mut lines, dimension, keyword)?);
+            }
+            "DISPLAY_DATA_SECTION" => {
+                let dimension = require_dimension(number, dimension)?;
+                display_coordinates = Some(read_coordinates(&mut lines, dimension, keyword)?);
+            }
+            "EDGE_WEIGHT_SECTION" => {
+                let dimension = require_dimension(number, dimension)?;
+                let format = edge_weight_format.as_deref().unwrap_or("");
+                let count = weight_count(format, dimension)
+                    .ok_or_else(|| unsupported(number, "EDGE_WEIGHT_FORMAT", format))?;
+                weights = Some(read_numbers(&mut lines, count, keyword)?);
+            }
+            "DEMAND_SECTION" => {
+                let dimension = require_dimension(number, dimension)?;
+                demands = Some(read_demands(&mut lines, dimension)?);
+            }
+            "DEPOT_SECTION" => {
+                let numbers = read_numbers::<f64, _>(&mut lines, 2, keyword)?;
+
+                if numbers[0] < 1.0 || numbers[1] != -1.0 {
+                    return Err(TsplibError::new("unsupported", "DEPOT_SECTION must give a single depot"));
+                }
+
+                depot = numbers[0] as usize - 1;
+            }
+            "EOF" => break,
+            _ => return Err(unsupported(number, "keyword", keyword)),
+        }
+    }
+
+    let dimension = dimension.ok_or_else(|| TsplibError::new("format", "DIMENSION is missing"))?;
+    let edge_weight_type = edge_weight_type.unwrap_or_default();
+
+    let distances = match edge_weight_type.as_str() {
+        "EXPLICIT" if asymmetric => {
+            if edge_weight_format.as_deref() != Some("FULL_MATRIX") {
+                return Err(TsplibError::new("unsupported", "an ATSP EDGE_WEIGHT_FORMAT must be FULL_MATRIX"));
+            }
+
+            let weights = weights
+                .ok_or_else(|| TsplibError::new("format", "EDGE_WEIGHT_SECTION is missing"))?;
+            SquareMatrix::full(dimension, |i, j| weights[i * dimension + j])
+        }
+        _ if asymmetric => return Err(TsplibError::new("unsupported", "an ATSP EDGE_WEIGHT_TYPE must be EXPLICIT")),
+        "EXPLICIT" => {
+            let weights = weights
+                .ok_or_else(|| TsplibError::new("format", "EDGE_WEIGHT_SECTION is missing"))?;
+            explicit_distances(edge_weight_format.as_deref().unwrap_or(""), dimension, &weights)
+        }
+        "EUC_2D" | "CEIL_2D" | "ATT" | "GEO" => {
+            let coordinates = coordinates
+                .as_ref()
+                .ok_or_else(|| TsplibError::new("format", "NODE_COORD_SECTION is missing"))?;
+            let distance = match edge_weight_type.as_str() {
+                "EUC_2D" => euc_2d,
+                "CEIL_2D" => ceil_2d,
+                "ATT" => att,
+                _ => geo,
+            };
+            SquareMatrix::symmetric(dimension, |i, j| {
+                if i == j {
+                    0.0
+                } else {
+                    distance(coordinates[i], coordinates[j])
+                }
+            })
+        }
+        other => return Err(TsplibError::new("unsupported", &format!("unsupported EDGE_WEIGHT_TYPE {}", other))),
+    };
+
+    // The positions of the cities are only used to display them.
+    let positions = display_coordinates.or(coordinates).unwrap_or_else(|| vec![(0.0, 0.0); dimension]);
+    let demands = demands.unwrap_or_else(|| vec![0.0; dimension]);
+    let cities: Vec<City> = positions
+        .iter()
+        .zip(demands)
+        .map(|(&(x, y), demand)| City::with_demand(Vector2::new(x as f32, y as f32), demand))
+        .collect();
+
+    let mut map = Map::with_distances(&cities, distances);
+    map.geometric = matches!(edge_weight_type.as_str(), "EUC_2D" | "CEIL_2D" | "ATT");
+
+    if vehicle_routing {
+        let capacity = capacity.ok_or_else(|| TsplibError::new("format", "CAPACITY is missing"))?;
+
+        if depot >= dimension {
+            return Err(TsplibError::new("format", &format!("DEPOT_SECTION: invalid node {}", depot + 1)));
+        }
+
+        map.fleet = Some(Fleet::new(depot, capacity));
+    }
+
+    Ok(Instance { name, comment, map })
+}
+
+/// Load a TSPLIB tour file (`.tour`). The first
+/// tour of the file is returned.
+///
+/// # Arguments
+/// * `path` - The path of the file.
+pub fn load_tour(path: &str) -> Result<Vec<usize>, TsplibError> {
+    parse_tour(&std::fs::read_to_string(path)?)
+}
+
+/// Parse the content of a TSPLIB tour file. The returned
+/// city indices start at `0` (TSPLIB ones start at `1`).
+///
+/// # Arguments
+/// * `content` - The content of the file.
+pub fn parse_tour(content: &str) -> Result<Vec<usize>, TsplibError> {
+    let mut dimension = None;
+    let mut lines = content.lines().enumerate();
+
+    while let Some((number, line)) = lines.next() {
+        let (keyword, value) = split_keyword(line);
+
+        match keyword {
+            "" | "NAME" | "COMMENT" => continue,
+            "TYPE" => {
+                if value != "TOUR" {
+                    return Err(unsupported(number, "TYPE", value));
+                }
+            }
+            "DIMENSION" => dimension = Some(parse_number::<usize>(number, value)?),
+            "TOUR_SECTION" => return read_tour(&mut lines, dimension),
+            "EOF" => break,
+            _ => return Err(unsupported(number, "keyword", keyword)),
+        }
+    }
+
+    Err(TsplibError::new("format", "TOUR_SECTION is missing"))
+}
+
+/// Save a tour in a TSPLIB tour file.
+///
+/// # Arguments
+/// * `path` - The path of the file.
+/// * `name` - The name of the tour.
+/// * `comment` - A comment about the tour (its length for example).
+/// * `tour` - The index (starting at `0`) of each city in the order they are visited.
+pub fn save_tour(path: &str, name: &str, comment: &str, tour: &[usize]) -> Result<(), TsplibError> {
+    std::fs::write(path, format_tour(name, comment, tour))?;
+    Ok(())
+}
+
+/// Returns a tour in the TSPLIB tour format.
+///
+/// # Arguments
+/// * `name` - The name of the tour.
+/// * `comment` - A comment about the tour (its length for example).
+/// * `tour` - The index (starting at `0`) of each city in the order they are visited.
+pub fn format_tour(name: &str, comment: &str, tour: &[usize]) -> String {
+    let mut content = format!("NAME : {}\n", name);
+
+    if !comment.is_empty() {
+        content.push_str(&format!("COMMENT : {}\n", comment));
+    }
+
+    content.push_str(&format!("TYPE : TOUR\nDIMENSION : {}\nTOUR_SECTION\n", tour.len()));
+
+    for city in tour {
+        content.push_str(&format!("{}\n", city + 1));
+    }
+
+    content.push_str("-1\nEOF\n");
+    content
+}
+
+/// Split a line into a keyword and its value (`KEYWORD : value`).
+pub(crate) fn split_keyword(line: &str) -> (&str, &str) {
+    match line.find(':') {
+        Some(index) => (line[..index].trim(), line[index + 1..].trim()),
+        None => (line.trim(), ""),
+    }
+}
+
+/// Parse the value found on a line.
+pub(crate) fn parse_number<T: std::str::FromStr>(number: usize, value: &str) -> Result<T, TsplibError> {
+    value.parse::<T>().map_err(|_| {
+        TsplibError::new("format", &format!("line {}: invalid value `{}`", number + 1, value))
+    })
+}
+
+fn require_dimension(number: usize, dimension: Option<usize>) -> Result<usize, TsplibError> {
+    dimension.ok_or_else(|| {
+        TsplibError::new("format", &format!("line {}: DIMENSION must be given first", number + 1))
+    })
+}
+
+fn unsupported(number: usize, what: &str, value: &str) -> TsplibError {
+    TsplibError::new("unsupported", &format!("line {}: unsupported {} `{}`", number + 1, what, value))
+}
+
+/// Read `count` values, spread on as many lines as needed.
+pub(crate) fn read_numbers<'a, T: std::str::FromStr, I: Iterator<Item = (usize, &'a str)>>(
+    lines: &mut I,
+    count: usize,
+    section: &str,
+) -> Result<Vec<T>, TsplibError> {
+    let mut numbers = Vec::with_capacity(count);
+
+    while numbers.len() < count {
+        let (number, line) = lines.next().ok_or_else(|| {
+            TsplibError::new(
+                "format",
+                &format!("{} ends after {} values, {} expected", section, numbers.len(), count),
+            )
+        })?;
+
+        for value in line.split_whitespace().take(count - numbers.len()) {
+            numbers.push(parse_number(number, value)?);
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Read the `index x y` lines of a coordinates section.
+fn read_coordinates<'a, I: Iterator<Item = (usize, &'a str)>>(
+    lines: &mut I,
+    dimension: usize,
+    section: &str,
+) -> Result<Vec<(f64, f64)>, TsplibError> {
+    let numbers = read_numbers::<f64, _>(lines, dimension * 3, section)?;
+    let mut coordinates = vec![(0.0, 0.0); dimension];
+
+    for node in numbers.chunks(3) {
+        let index = node[0] as usize;
+
+        if index < 1 || index > dimension {
+            return Err(TsplibError::new("format", &format!("{}: invalid node {}", section, node[0])));
+        }
+
+        coordinates[index - 1] = (node[1], node[2]);
+    }
+
+    Ok(coordinates)
+}
+
+/// Read the `index demand` lines of a demand section.
+fn read_demands<'a, I: Iterator<Item = (usize, &'a str)>>(
+    lines: &mut I,
+    dimension: usize,
+) -> Result<Vec<f64>, TsplibError> {
+    let numbers = read_numbers::<f64, _>(lines, dimension * 2, "DEMAND_SECTION")?;
+    let mut demands = vec![0.0; dimension];
+
+    for node in numbers.chunks(2) {
+        let index = node[0] as usize;
+
+        if index < 1 || index > dimension {
+            return Err(TsplibError::new("format", &format!("DEMAND_SECTION: invalid node {}", node[0])));
+        }
+
+        demands[index - 1] = node[1];
+    }
+
+    Ok(demands)
+}
+
+/// Read a tour section, terminated by `-1`.
+fn read_tour<'a, I: Iterator<Item = (usize, &'a str)>>(
+    lines: &mut I,
+    dimension: Option<usize>,
+) -> Result<Vec<usize>, TsplibError> {
+    let mut tour = Vec::new();
+
+    for (number, line) in lines {
+        for value in line.split_whitespace() {
+            let city = parse_number::<i64>(number, value)?;
+
+            if city == -1 {
+                return check_tour(tour, dimension);
+            }
+
+            if city < 1 {
+                return Err(TsplibError::new("format", &format!("line {}: invalid city {}", number + 1, city)));
+            }
+
+            tour.push(city as usize - 1);
+        }
+    }
+
+    // Some files miss the final `-1`...
+    check_tour(tour, dimension)
+}
+
+/// Check that a tour visits each city once.
+fn check_tour(tour: Vec<usize>, dimension: Option<usize>) -> Result<Vec<usize>, TsplibError> {
+    let dimension = dimension.unwrap_or(tour.len());
+    let mut visited = vec![false; dimension];
+
+    if tour.len() != dimension {
+        return Err(TsplibError::new(
+            "format",
+            &format!("the tour visits {} cities, {} expected", tour.len(), dimension),
+        ));
+    }
+
+    for &city in tour.iter() {
+        if city >= dimension || visited[city] {
+            return Err(TsplibError::new("format", &format!("city {} is invalid or visited twice", city + 1)));
+        }
+        visited[city] = true;
+    }
+
+    Ok(tour)
+}
+
+/// Returns the number of weights in an `EDGE_WEIGHT_SECTION`.
+fn weight_count(format: &str, dimension: usize) -> Option<usize> {
+    match format {
+        "FULL_MATRIX" => Some(dimension * dimension),
+        "UPPER_ROW" | "LOWER_ROW" => Some(dimension * dimension.saturating_sub(1) / 2),
+        "UPPER_DIAG_ROW" | "LOWER_DIAG_ROW" => Some(dimension * (dimension + 1) / 2),
+        _ => None,
+    }
+}
+
+/// Build the distance matrix from the weights of an `EDGE_WEIGHT_SECTION`.
+fn explicit_distances(format: &str, dimension: usize, weights: &[f64]) -> SquareMatrix<f64> {
+    let mut distances = SquareMatrix::symmetric(dimension, |_, _| 0.0);
+    let mut weights = weights.iter();
+
+    for i in 0..dimension {
+        let columns = match format {
+            "FULL_MATRIX" => 0..dimension,
+            "UPPER_ROW" => i + 1..dimension,
+            "LOWER_ROW" => 0..i,
+            "UPPER_DIAG_ROW" => i..dimension,
+            _ => 0..i + 1,
+        };
+
+        for j in columns {
+            let weight = *weights.next().unwrap();
+
+            // Only the lower triangle of a full matrix is kept.
+            if format != "FULL_MATRIX" || j <= i {
+                distances.set(i, j, weight);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Rounds to the nearest integer, as TSPLIB does.
+fn nint(x: f64) -> f64 {
+    (x + 0.5).floor()
+}
+
+fn euc_2d(a: (f64, f64), b: (f64, f64)) -> f64 {
+    nint((a.0 - b.0).hypot(a.1 - b.1))
+}
+
+fn ceil_2d(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1).ceil()
+}
+
+/// The pseudo-euclidean distance.
+fn att(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+    let r = ((dx * dx + dy * dy) / 10.0).sqrt();
+    let t = nint(r);
+
+    if t < r {
+        t + 1.0
+    } else {
+        t
+    }
+}
+
+/// The geographical distance, the coordinates are
+/// given as `DDD.MM` (degrees and minutes).
+fn geo(a: (f64, f64), b: (f64, f64)) -> f64 {
+    // TSPLIB uses these rounded constants...
+    #[allow(clippy::approx_constant)]
+    const PI: f64 = 3.141592;
+    const RRR: f64 = 6378.388;
+
+    let radians = |x: f64| {
+        let degrees = x.trunc();
+        PI * (degrees + 5.0 * (x - degrees) / 3.0) / 180.0
+    };
+
+    let (latitude_a, longitude_a) = (radians(a.0), radians(a.1));
+    let (latitude_b, longitude_b) = (radians(b.0), radians(b.1));
+
+    let q1 = (longitude_a - longitude_b).cos();
+    let q2 = (latitude_a - latitude_b).cos();
+    let q3 = (latitude_a + latitude_b).cos();
+
+    let cosine = (0.5 * ((1.0 + q1) * q2 - (1.0 - q1) * q3)).clamp(-1.0, 1.0);
+
+    (RRR * cosine.acos() + 1.0).trunc()
+}
@@ -0,0 +1,275 @@
+use crate::local_search::{neighbours_of, or_opt, segment_insertion, two_opt, LocalSearch, EPSILON};
+use crate::map::Map;
+
+/// The vehicles of a capacitated vehicle routing problem. Each
+/// vehicle leaves the depot, delivers some cities and goes back
+/// to the depot, without carrying more than its capacity.
+///
+/// A solution is written as a single tour that starts at the depot
+/// and visits it again between two routes (a giant tour), so the
+/// length of the routes is the length of the tour.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fleet {
+    /// The index of the city where every route starts and ends.
+    pub depot: usize,
+
+    /// The total demand a vehicle can deliver on a route.
+    pub capacity: f64,
+}
+
+impl Fleet {
+    /// Create new fleet.
+    ///
+    /// # Arguments
+    /// * `depot` - The index of the city where every route starts and ends.
+    /// * `capacity` - The total demand a vehicle can deliver on a route.
+    pub fn new(depot: usize, capacity: f64) -> Fleet {
+        Fleet { depot, capacity }
+    }
+}
+
+/// Returns the routes of a giant tour, each one starts at the depot
+/// (which is only written once per route) and goes back to it.
+///
+/// # Arguments
+/// * `tour` - A giant tour that starts at the depot.
+/// * `depot` - The index of the depot.
+pub fn routes(tour: &[usize], depot: usize) -> Vec<Vec<usize>> {
+    let mut routes: Vec<Vec<usize>> = Vec::new();
+
+    for &city in tour.iter() {
+        if city == depot || routes.is_empty() {
+            routes.push(vec![depot]);
+        }
+
+        if city != depot {
+            routes.last_mut().unwrap().push(city);
+        }
+    }
+
+    routes.retain(|route| route.len() > 1);
+    routes
+}
+
+/// Returns the giant tour of some routes, the routes
+/// that don't deliver any city are left out.
+///
+/// # Arguments
+/// * `routes` - The routes, each one starts at the depot.
+pub fn giant_tour(routes: &[Vec<usize>]) -> Vec<usize> {
+    routes.iter().filter(|route| route.len() > 1).flatten().copied().collect()
+}
+
+/// Returns what is delivered on a route.
+///
+/// # Arguments
+/// * `map` - A map.
+/// * `route` - The cities of the route.
+pub fn load(map: &Map, route: &[usize]) -> f64 {
+    route.iter().map(|&city| map.cities[city].demand).sum()
+}
+
+/// Returns `true` if a giant tour delivers every city once,
+/// without overloading a vehicle.
+///
+/// # Arguments
+/// * `map` - A map.
+/// * `fleet` - The vehicles.
+/// * `tour` - A giant tour that starts at the depot.
+pub fn is_feasible(map: &Map, fleet: &Fleet, tour: &[usize]) -> bool {
+    let mut visited = vec![false; map.cities.len()];
+
+    for &city in tour.iter().filter(|&&city| city != fleet.depot) {
+        if visited[city] {
+            return false
+        }
+
+        visited[city] = true;
+    }
+
+    let every_city = (0..map.cities.len()).all(|city| visited[city] || city == fleet.depot);
+
+    every_city
+        && tour.first() == Some(&fleet.depot)
+        && routes(tour, fleet.depot).iter().all(|route| load(map, route) <= fleet.capacity + EPSILON)
+}
+
+/// Improve the routes of a giant tour: the enabled moves of `local_search`
+/// are applied inside each route, and cities are moved or swapped between
+/// two routes when it shortens them and the vehicles can carry them.
+/// Returns `true` if the routes were improved.
+///
+/// # Arguments
+/// * `local_search` - The moves used inside each route.
+/// * `map` - A map.
+/// * `fleet` - The vehicles.
+/// * `neighbours` - The nearest neighbours of each city (see `Map::nearest_neighbours`).
+/// * `tour` - The giant tour to improve.
+pub fn improve_routes(
+    local_search: &LocalSearch,
+    map: &Map,
+    fleet: &Fleet,
+    neighbours: &[Vec<usize>],
+    tour: &mut Vec<usize>,
+) -> bool {
+    let mut routes = routes(tour, fleet.depot);
+    let mut improved = false;
+
+    loop {
+        let mut changed = false;
+
+        for route in routes.iter_mut() {
+            if local_search.two_opt {
+                changed |= two_opt(map, route, neighbours);
+            }
+
+            if local_search.or_opt {
+                changed |= or_opt(map, route, neighbours);
+            }
+
+            if local_search.segment_insertion {
+                changed |= segment_insertion(map, route, neighbours, local_search.segment_length);
+            }
+
+            // The moves may have rotated the route...
+            if let Some(depot) = route.iter().position(|&city| city == fleet.depot) {
+                route.rotate_left(depot);
+            }
+        }
+
+        if local_search.inter_route {
+            changed |= relocate(map, fleet, neighbours, &mut routes);
+            changed |= exchange(map, fleet, neighbours, &mut routes);
+        }
+
+        if !changed {
+            break
+        }
+
+        improved = true;
+    }
+
+    if improved {
+        *tour = giant_tour(&routes);
+    }
+
+    improved
+}
+
+/// Where each city is: its route and its position in the route.
+fn locations(map: &Map, routes: &[Vec<usize>]) -> Vec<Option<(usize, usize)>> {
+    let mut location = vec![None; map.cities.len()];
+
+    for (r, route) in routes.iter().enumerate() {
+        for (i, &city) in route.iter().enumerate().skip(1) {
+            location[city] = Some((r, i));
+        }
+    }
+
+    location
+}
+
+/// Returns the cities before and after a position of a route.
+fn around(route: &[usize], i: usize) -> (usize, usize) {
+    (route[i - 1], route[(i + 1) % route.len()])
+}
+
+/// Move a city next to one of its neighbours in another route, when it
+/// shortens the routes and the other vehicle can carry the city.
+fn relocate(map: &Map, fleet: &Fleet, neighbours: &[Vec<usize>], routes: &mut [Vec<usize>]) -> bool {
+    let mut location = locations(map, routes);
+    let mut loads: Vec<f64> = routes.iter().map(|route| load(map, route)).collect();
+    let mut improved = false;
+
+    for city in 0..map.cities.len() {
+        let (r, i) = match location[city] {
+            Some(location) => location,
+            None => continue,
+        };
+
+        let demand = map.cities[city].demand;
+        let (before, after) = around(&routes[r], i);
+        let removal = map.distance(before, city) + map.distance(city, after) - map.distance(before, after);
+
+        let (mut best, mut best_gain) = (None, EPSILON);
+
+        for &other in neighbours_of(neighbours, city) {
+            let (s, j) = match location[other] {
+                Some((s, j)) if s != r && loads[s] + demand <= fleet.capacity + EPSILON => (s, j),
+                _ => continue,
+            };
+
+            // Try to put the city before and after its neighbour...
+            let (previous, next) = around(&routes[s], j);
+
+            for &(from, to, at) in [(previous, other, j), (other, next, j + 1)].iter() {
+                let gain = removal - (map.distance(from, city) + map.distance(city, to) - map.distance(from, to));
+
+                if gain > best_gain {
+                    best = Some((s, at));
+                    best_gain = gain;
+                }
+            }
+        }
+
+        if let Some((s, at)) = best {
+            routes[r].remove(i);
+            routes[s].insert(at, city);
+            loads[r] -= demand;
+            loads[s] += demand;
+            location = locations(map, routes);
+            improved = true;
+        }
+    }
+
+    improved
+}
+
+/// Swap a city with one of its neighbours in another route, when it
+/// shortens the routes and both vehicles can carry their new cities.
+fn exchange(map: &Map, fleet: &Fleet, neighbours: &[Vec<usize>], routes: &mut [Vec<usize>]) -> bool {
+    let mut location = locations(map, routes);
+    let mut loads: Vec<f64> = routes.iter().map(|route| load(map, route)).collect();
+    let mut improved = false;
+
+    // The change of length when `city` takes the place of `replaced`.
+    let delta = |before: usize, after: usize, replaced: usize, city: usize| {
+        map.distance(before, city) + map.distance(city, after) - map.distance(before, replaced) - map.distance(replaced, after)
+    };
+
+    for a in 0..map.cities.len() {
+        let (r, i) = match location[a] {
+            Some(location) => location,
+            None => continue,
+        };
+
+        for &b in neighbours_of(neighbours, a) {
+            let (s, j) = match location[b] {
+                Some((s, j)) if s != r => (s, j),
+                _ => continue,
+            };
+
+            let difference = map.cities[b].demand - map.cities[a].demand;
+
+            if loads[r] + difference > fleet.capacity + EPSILON || loads[s] - difference > fleet.capacity + EPSILON {
+                continue
+            }
+
+            let (before_a, after_a) = around(&routes[r], i);
+            let (before_b, after_b) = around(&routes[s], j);
+
+            if delta(before_a, after_a, a, b) + delta(before_b, after_b, b, a) < -EPSILON {
+                routes[r][i] = b;
+                routes[s][j] = a;
+                loads[r] += difference;
+                loads[s] -= difference;
+                location[a] = Some((s, j));
+                location[b] = Some((r, i));
+                improved = true;
+                break
+            }
+        }
+    }
+
+    improved
+}
@@ -0,0 +1,102 @@
+use luve_rust::ant::Ant;
+use luve_rust::map::Map;
+use luve_rust::params::AcoParams;
+use luve_rust::strategy::{AcsParams, Strategy};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+
+use common::random_map;
+
+/// Four cities where, from the first one, the attraction of the
+/// others is only given by the pheromones: 1, 2 and 5.
+fn weighted_map() -> Map {
+    let mut map = random_map(4, 3);
+
+    map.set_beta(0.0);
+    map.pheromones[0][1] = 1.0;
+    map.pheromones[0][2] = 2.0;
+    map.pheromones[0][3] = 5.0;
+    map
+}
+
+/// Returns the city chosen by an ant that leaves the first city.
+fn first_choice(map: &Map, params: &AcoParams, rng: &mut StdRng) -> usize {
+    let mut ant = Ant::new(0);
+    ant.explore_map(map, params, rng);
+    ant.tour[1]
+}
+
+#[test]
+fn roulette_wheel_follows_the_attractions() {
+    let map = weighted_map();
+    let params = AcoParams { alpha: 1.0, ..AcoParams::default() };
+    let mut rng = StdRng::seed_from_u64(4);
+    let mut counts = [0; 4];
+    let draws = 40_000;
+
+    for _ in 0..draws {
+        counts[first_choice(&map, &params, &mut rng)] += 1;
+    }
+
+    assert_eq!(counts[0], 0);
+
+    for &(city, weight) in [(1, 1.0), (2, 2.0), (3, 5.0)].iter() {
+        let frequency = counts[city] as f64 / draws as f64;
+
+        assert!((frequency - weight / 8.0).abs() < 0.01, "city {}: {}", city, frequency);
+    }
+}
+
+#[test]
+fn alpha_raises_the_pheromones() {
+    let map = weighted_map();
+    let params = AcoParams { alpha: 2.0, ..AcoParams::default() };
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut counts = [0; 4];
+    let draws = 40_000;
+
+    for _ in 0..draws {
+        counts[first_choice(&map, &params, &mut rng)] += 1;
+    }
+
+    // The weights are now 1, 4 and 25.
+    let frequency = counts[3] as f64 / draws as f64;
+
+    assert!((frequency - 25.0 / 30.0).abs() < 0.01, "{}", frequency);
+}
+
+#[test]
+fn exploitation_always_takes_the_most_attractive_city() {
+    let map = weighted_map();
+    let params = AcoParams { alpha: 1.0, q0: 1.0, ..AcoParams::default() };
+    let acs = AcoParams {
+        alpha: 1.0,
+        strategy: Strategy::AntColonySystem(AcsParams { q0: 1.0, ..AcsParams::default() }),
+        ..AcoParams::default()
+    };
+
+    for seed in 0..200 {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        assert_eq!(first_choice(&map, &params, &mut rng), 3);
+        assert_eq!(first_choice(&map, &acs, &mut rng), 3);
+    }
+}
+
+#[test]
+fn exploitation_takes_the_distance_into_account() {
+    let mut map = random_map(12, 6);
+    let params = AcoParams { q0: 1.0, ..AcoParams::default() };
+
+    map.set_beta(params.beta);
+
+    let nearest = (1..12)
+        .min_by(|&a, &b| map.distance(0, a).partial_cmp(&map.distance(0, b)).unwrap())
+        .unwrap();
+
+    for seed in 0..50 {
+        assert_eq!(first_choice(&map, &params, &mut StdRng::seed_from_u64(seed)), nearest);
+    }
+}
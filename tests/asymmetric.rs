@@ -0,0 +1,79 @@
+use cgmath::Vector2;
+use luve_rust::city::City;
+use luve_rust::colony::Colony;
+use luve_rust::local_search::{or_opt, segment_insertion, two_opt, LocalSearch, LocalSearchScope};
+use luve_rust::map::Map;
+use luve_rust::matrix::SquareMatrix;
+use luve_rust::params::AcoParams;
+
+mod common;
+
+use common::{assert_permutation, asymmetric_map, random_tour};
+
+#[test]
+fn full_matrix_keeps_both_directions() {
+    let cities: Vec<City> = (0..3).map(|i| City::new(Vector2::new(i as f32, 0.0))).collect();
+    let map = Map::with_distances(&cities, SquareMatrix::full(3, |i, j| (10 * i + j) as f64));
+
+    assert!(!map.is_symmetric());
+    assert_eq!(map.distance(0, 1), 1.0);
+    assert_eq!(map.distance(1, 0), 10.0);
+    assert_eq!(map.distance(1, 2), 12.0);
+    assert_eq!(map.distance(2, 1), 21.0);
+    assert_eq!(map.tour_length(&[0, 1, 2]), 1.0 + 12.0 + 20.0);
+    assert_eq!(map.tour_length(&[0, 2, 1]), 2.0 + 21.0 + 10.0);
+}
+
+#[test]
+fn moves_never_lengthen_the_directed_tour() {
+    for seed in 0..10 {
+        let map = asymmetric_map(40, seed);
+        let neighbours = map.nearest_neighbours(10);
+
+        for step in 0..3 {
+            let mut tour = random_tour(&map, seed);
+            let before = map.tour_length(&tour);
+
+            match step {
+                0 => two_opt(&map, &mut tour, &neighbours),
+                1 => or_opt(&map, &mut tour, &neighbours),
+                _ => segment_insertion(&map, &mut tour, &neighbours, 10),
+            };
+
+            assert_permutation(&tour, 40);
+            assert!(map.tour_length(&tour) <= before + 1e-9);
+        }
+    }
+}
+
+#[test]
+fn improve_never_lengthens_the_directed_tour() {
+    let local_search = LocalSearch { segment_insertion: true, ..LocalSearch::default() };
+
+    for seed in 0..10 {
+        let map = asymmetric_map(40, seed);
+        let neighbours = map.nearest_neighbours(10);
+        let mut tour = random_tour(&map, seed + 100);
+        let before = map.tour_length(&tour);
+        let improved = local_search.improve(&map, &neighbours, &mut tour);
+
+        assert_permutation(&tour, 40);
+        assert!(improved);
+        assert!(map.tour_length(&tour) < before);
+    }
+}
+
+#[test]
+fn colony_reports_the_directed_length() {
+    let params = AcoParams {
+        iterations: 20,
+        seed: Some(3),
+        local_search: LocalSearch { scope: LocalSearchScope::EveryAnt, ..LocalSearch::default() },
+        ..AcoParams::default()
+    };
+    let mut colony = Colony::new(asymmetric_map(25, 7), params).unwrap();
+    let result = colony.run();
+
+    assert_permutation(&result.best_tour, 25);
+    assert!((colony.map.tour_length(&result.best_tour) - result.best_length).abs() < 1e-9);
+}
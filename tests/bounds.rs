@@ -0,0 +1,59 @@
+use luve_rust::bounds::{gap, held_karp_bound, mst_bound, one_tree_bound};
+use luve_rust::exact::held_karp;
+use luve_rust::map::{Map, TourMode};
+
+mod common;
+
+use common::{asymmetric_map, random_map};
+
+/// Check that the bounds are ordered and below the optimal tour.
+fn assert_bounds_are_ordered(map: &Map) {
+    let optimum = held_karp(map).unwrap().length;
+    let mst = mst_bound(map);
+    let one_tree = one_tree_bound(map);
+    let held_karp = held_karp_bound(map, 1000);
+
+    assert!(mst <= one_tree + 1e-6, "{} > {}", mst, one_tree);
+    assert!(one_tree <= held_karp + 1e-6, "{} > {}", one_tree, held_karp);
+    assert!(held_karp <= optimum + 1e-6, "{} > {}", held_karp, optimum);
+}
+
+#[test]
+fn bounds_are_below_the_optimum() {
+    for seed in 0..8 {
+        assert_bounds_are_ordered(&random_map(5 + seed as usize, seed));
+    }
+}
+
+#[test]
+fn bounds_are_below_the_optimum_of_an_asymmetric_map() {
+    for seed in 0..5 {
+        let map = asymmetric_map(9, seed);
+
+        assert!(!map.is_symmetric());
+        assert_bounds_are_ordered(&map);
+    }
+}
+
+#[test]
+fn held_karp_bound_is_close_to_the_optimum() {
+    let map = random_map(14, 21);
+    let optimum = held_karp(&map).unwrap().length;
+
+    assert!(gap(optimum, held_karp_bound(&map, 1000)) < 5.0);
+}
+
+#[test]
+fn open_tours_fall_back_to_the_mst_bound() {
+    let mut map = random_map(10, 3);
+    map.mode = TourMode::Open;
+
+    assert_eq!(one_tree_bound(&map), mst_bound(&map));
+    assert_eq!(held_karp_bound(&map, 100), mst_bound(&map));
+}
+
+#[test]
+fn gap_is_a_percentage_of_the_bound() {
+    assert_eq!(gap(110.0, 100.0), 10.0);
+    assert_eq!(gap(100.0, 100.0), 0.0);
+}
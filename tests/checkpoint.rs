@@ -0,0 +1,179 @@
+use cgmath::Vector2;
+use luve_rust::checkpoint::{format_checkpoint, parse_checkpoint};
+use luve_rust::city::City;
+use luve_rust::colony::Colony;
+use luve_rust::local_search::{LocalSearch, LocalSearchScope};
+use luve_rust::map::Map;
+use luve_rust::params::AcoParams;
+use luve_rust::schedule::TimeWindow;
+use luve_rust::stats::History;
+use luve_rust::strategy::{AcsParams, MaxMinParams, Strategy};
+use luve_rust::vrp::Fleet;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+mod common;
+
+use common::{asymmetric_map, random_map};
+
+/// Returns the statistics of a history, without the times.
+fn statistics(history: &History) -> Vec<(usize, f64, f64, f64, f64, f64, bool)> {
+    history
+        .iterations
+        .iter()
+        .map(|stats| {
+            (
+                stats.iteration,
+                stats.best_length,
+                stats.mean_length,
+                stats.worst_length,
+                stats.global_best,
+                stats.branching_factor,
+                stats.reset,
+            )
+        })
+        .collect()
+}
+
+/// Run `iterations` iterations at once, then again with a checkpoint
+/// after `k` iterations, and check that both runs are the same.
+fn assert_resumes(map: fn() -> Map, params: AcoParams, k: usize) -> Colony {
+    let mut uninterrupted = Colony::with_seed(map(), params.clone(), 17).unwrap();
+    uninterrupted.run();
+
+    let mut first = Colony::with_seed(map(), params, 17).unwrap();
+
+    for _ in 0..k {
+        first.iterate();
+    }
+
+    let content = format_checkpoint(&first);
+    let mut resumed = parse_checkpoint(&content).unwrap();
+
+    assert_eq!(format_checkpoint(&resumed), content);
+
+    resumed.run();
+
+    assert_eq!(resumed.iteration, uninterrupted.iteration);
+    assert_eq!(resumed.best_tour, uninterrupted.best_tour);
+    assert_eq!(resumed.best_length, uninterrupted.best_length);
+    assert_eq!(statistics(&resumed.history), statistics(&uninterrupted.history));
+    assert_eq!(resumed.map.pheromones, uninterrupted.map.pheromones);
+
+    resumed
+}
+
+fn params(strategy: Strategy) -> AcoParams {
+    AcoParams {
+        iterations: 40,
+        ant_count: 15,
+        strategy,
+        ..AcoParams::default()
+    }
+}
+
+#[test]
+fn ant_system_resumes_exactly() {
+    assert_resumes(|| random_map(30, 1), params(Strategy::AntSystem), 13);
+}
+
+#[test]
+fn max_min_resumes_exactly() {
+    // Resets every few iterations, so the checkpoint falls between two of them.
+    let max_min = MaxMinParams { reset_delay: 4, branching_threshold: 10.0, ..MaxMinParams::default() };
+    let resumed = assert_resumes(|| random_map(30, 2), params(Strategy::MaxMin(max_min)), 21);
+
+    assert!(resumed.history.iterations.iter().filter(|stats| stats.reset).count() >= 2);
+}
+
+#[test]
+fn ant_colony_system_resumes_exactly() {
+    let mut params = params(Strategy::AntColonySystem(AcsParams::default()));
+    params.local_search = LocalSearch { scope: LocalSearchScope::IterationBest, ..LocalSearch::default() };
+
+    assert_resumes(|| random_map(30, 3), params, 9);
+}
+
+#[test]
+fn vehicle_routing_with_time_windows_resumes_exactly() {
+    fn map() -> Map {
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut cities: Vec<City> = (0..20)
+            .map(|_| {
+                let position = Vector2::new(rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0));
+                City::with_demand(position, rng.gen_range(1.0..10.0))
+            })
+            .collect();
+
+        cities[0].demand = 0.0;
+
+        for city in cities.iter_mut().skip(1) {
+            let earliest = rng.gen_range(0.0..200.0);
+            city.time_window = Some(TimeWindow::new(earliest, earliest + 60.0));
+            city.service_time = 5.0;
+        }
+
+        let mut map = Map::new(&cities);
+        map.fleet = Some(Fleet::new(0, 30.0));
+        map.lateness_penalty = 7.5;
+        map
+    }
+
+    let mut params = params(Strategy::AntSystem);
+    params.local_search.scope = LocalSearchScope::EveryAnt;
+
+    let resumed = assert_resumes(map, params, 11);
+    let original = map();
+
+    assert_eq!(resumed.map.fleet, original.fleet);
+    assert_eq!(resumed.map.lateness_penalty, 7.5);
+
+    for (city, expected) in resumed.map.cities.iter().zip(original.cities.iter()) {
+        assert_eq!(city.demand, expected.demand);
+        assert_eq!(city.service_time, expected.service_time);
+        assert_eq!(city.time_window, expected.time_window);
+    }
+}
+
+#[test]
+fn asymmetric_map_resumes_exactly() {
+    let resumed = assert_resumes(|| asymmetric_map(15, 5), params(Strategy::MaxMin(MaxMinParams::default())), 20);
+    let original = asymmetric_map(15, 5);
+
+    assert!(!resumed.map.is_symmetric());
+
+    for i in 0..15 {
+        for j in 0..15 {
+            assert_eq!(resumed.map.distance(i, j), original.distance(i, j));
+        }
+    }
+}
+
+#[test]
+fn invalid_durations_are_rejected() {
+    let mut colony = Colony::with_seed(random_map(10, 3), params(Strategy::AntSystem), 17).unwrap();
+    colony.iterate();
+
+    let content = format_checkpoint(&colony);
+    let lines: Vec<&str> = content.lines().collect();
+    let limit = lines.iter().position(|line| line.starts_with("TIME_LIMIT:")).unwrap();
+    let stats = lines.len() - 2;
+
+    for value in ["-1", "NaN", "inf"].iter() {
+        let mut changed = lines.clone();
+        let time_limit = format!("TIME_LIMIT: {}", value);
+        changed[limit] = &time_limit;
+
+        let error = parse_checkpoint(&changed.join("\n")).err().unwrap();
+        assert_eq!(error.message, format!("line {}: invalid value `{}`", limit + 1, value));
+
+        let mut changed = lines.clone();
+        let mut values: Vec<&str> = lines[stats].split_whitespace().collect();
+        values[7] = value;
+        let history = values.join(" ");
+        changed[stats] = &history;
+
+        let error = parse_checkpoint(&changed.join("\n")).err().unwrap();
+        assert_eq!(error.message, format!("line {}: invalid value `{}`", stats + 1, value));
+    }
+}
@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Write a file in the temporary directory and returns its path.
+fn write(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("luve_rust_cli_{}", name));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+/// Run the solver and returns its exit code.
+fn exit_code(args: &[&str]) -> i32 {
+    Command::new(env!("CARGO_BIN_EXE_ants_algorithm"))
+        .args(args)
+        .output()
+        .unwrap()
+        .status
+        .code()
+        .unwrap()
+}
+
+/// Write a square instance, each test gives its own `name` since the
+/// tests run in parallel.
+fn square(name: &str) -> String {
+    write(&format!("{}.csv", name), "0,0\n0,10\n10,10\n10,0\n").to_string_lossy().into_owned()
+}
+
+#[test]
+fn solves_an_instance() {
+    assert_eq!(exit_code(&[&square("solved"), "-i", "5", "--seed", "1", "-q"]), 0);
+    assert_eq!(exit_code(&["--help"]), 0);
+}
+
+#[test]
+fn invalid_arguments_exit_with_2() {
+    let square = square("invalid_arguments");
+
+    assert_eq!(exit_code(&[]), 2);
+    assert_eq!(exit_code(&[&square, "--unknown"]), 2);
+    assert_eq!(exit_code(&[&square, "--alpha", "abc"]), 2);
+    assert_eq!(exit_code(&[&square, "--rho", "2"]), 2);
+    assert_eq!(exit_code(&[&square, "--mode", "0-9"]), 2);
+    assert_eq!(exit_code(&[&square, "-a", "unknown"]), 2);
+}
+
+#[test]
+fn invalid_instances_exit_with_3() {
+    let missing = std::env::temp_dir().join("luve_rust_cli_missing.tsp");
+    let unparsable = write("unparsable.csv", "0,0\n1,a\n");
+    let deep = write("deep.json", &"[".repeat(200_000));
+    let empty = write("empty.tsp", "TYPE: TSP\nDIMENSION: 0\nEDGE_WEIGHT_TYPE: EUC_2D\nNODE_COORD_SECTION\nEOF\n");
+    let overloaded = write(
+        "overloaded.vrp",
+        "TYPE: CVRP\nDIMENSION: 2\nEDGE_WEIGHT_TYPE: EUC_2D\nCAPACITY: 5\nNODE_COORD_SECTION\n1 0 0\n2 3 4\n\
+         DEMAND_SECTION\n1 0\n2 10\nDEPOT_SECTION\n1\n-1\nEOF\n",
+    );
+
+    assert_eq!(exit_code(&[&missing.to_string_lossy()]), 3);
+    assert_eq!(exit_code(&[&unparsable.to_string_lossy()]), 3);
+    assert_eq!(exit_code(&[&deep.to_string_lossy()]), 3);
+    assert_eq!(exit_code(&[&empty.to_string_lossy()]), 3);
+    assert_eq!(exit_code(&[&overloaded.to_string_lossy()]), 3);
+}
+
+#[test]
+fn unwritable_outputs_exit_with_4() {
+    let tour = std::env::temp_dir().join("luve_rust_cli_missing_directory").join("square.tour");
+
+    assert_eq!(exit_code(&[&square("unwritable_outputs"), "-i", "2", "-q", "-o", &tour.to_string_lossy()]), 4);
+}
+
+#[test]
+fn baseline_is_reported_next_to_the_colony() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ants_algorithm"))
+        .args([&square("baseline"), "-i", "5", "--seed", "1", "--baseline"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+
+    for name in ["Nearest neighbour", "Greedy edge", "Cheapest insertion", "Farthest insertion", "Double tree"].iter() {
+        assert!(stdout.contains(&format!("{} : 40.00", name)), "{}", stdout);
+    }
+
+    assert_eq!(exit_code(&[&square("baseline"), "--baseline", "--mode", "0-2"]), 2);
+    assert_eq!(exit_code(&[&square("baseline"), "--baseline", "-q"]), 2);
+}
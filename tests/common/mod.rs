@@ -0,0 +1,53 @@
+//! Fixtures shared by the integration tests.
+
+#![allow(dead_code)]
+
+use cgmath::Vector2;
+use luve_rust::city::City;
+use luve_rust::map::Map;
+use luve_rust::matrix::SquareMatrix;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Build a map with `count` cities at random positions.
+pub fn random_map(count: usize, seed: u64) -> Map {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let cities: Vec<City> = (0..count)
+        .map(|_| City::new(Vector2::new(rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0))))
+        .collect();
+
+    Map::new(&cities)
+}
+
+/// A 3 by 4 rectangle, its tour around the corners is 14 long.
+pub fn rectangle() -> Map {
+    let corners = [(0.0, 0.0), (0.0, 3.0), (4.0, 3.0), (4.0, 0.0)];
+    let cities: Vec<City> = corners.iter().map(|&(x, y)| City::new(Vector2::new(x, y))).collect();
+
+    Map::new(&cities)
+}
+
+/// Build a map of `count` cities with random directed distances.
+pub fn asymmetric_map(count: usize, seed: u64) -> Map {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let distances: Vec<f64> = (0..count * count).map(|_| rng.gen_range(1.0..100.0)).collect();
+    let cities: Vec<City> = (0..count).map(|i| City::new(Vector2::new(i as f32, 0.0))).collect();
+
+    Map::with_distances(&cities, SquareMatrix::full(count, |i, j| distances[i * count + j]))
+}
+
+/// Returns a random tour of the map.
+pub fn random_tour(map: &Map, seed: u64) -> Vec<usize> {
+    let mut tour: Vec<usize> = (0..map.cities.len()).collect();
+    tour.shuffle(&mut StdRng::seed_from_u64(seed));
+    tour
+}
+
+/// Check that a tour visits every city once.
+pub fn assert_permutation(tour: &[usize], count: usize) {
+    let mut sorted = tour.to_vec();
+    sorted.sort_unstable();
+
+    assert_eq!(sorted, (0..count).collect::<Vec<_>>());
+}
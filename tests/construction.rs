@@ -0,0 +1,67 @@
+use luve_rust::construction::{
+    cheapest_insertion, double_tree, farthest_insertion, greedy_edge, nearest_neighbour,
+};
+use luve_rust::exact::held_karp;
+use luve_rust::map::Map;
+
+mod common;
+
+use common::{assert_permutation, asymmetric_map, random_map};
+
+/// Returns the tours of every construction heuristic.
+fn tours(map: &Map) -> Vec<(&'static str, Vec<usize>)> {
+    vec![
+        ("nearest_neighbour", nearest_neighbour(map, map.cities.len() / 2)),
+        ("greedy_edge", greedy_edge(map)),
+        ("cheapest_insertion", cheapest_insertion(map)),
+        ("farthest_insertion", farthest_insertion(map)),
+        ("double_tree", double_tree(map)),
+    ]
+}
+
+#[test]
+fn heuristics_visit_every_city_once() {
+    for &count in [1, 2, 3, 10, 60].iter() {
+        let map = random_map(count, count as u64);
+
+        for (name, tour) in tours(&map) {
+            assert_eq!(tour.len(), count, "{}", name);
+            assert_permutation(&tour, count);
+        }
+    }
+
+    assert!(tours(&Map::new(&[])).iter().all(|(_, tour)| tour.is_empty()));
+}
+
+#[test]
+fn heuristics_visit_every_city_of_an_asymmetric_map() {
+    let map = asymmetric_map(25, 9);
+
+    for (name, tour) in tours(&map) {
+        assert_eq!(tour.len(), 25, "{}", name);
+        assert_permutation(&tour, 25);
+    }
+}
+
+#[test]
+fn nearest_neighbour_starts_at_the_given_city() {
+    let map = random_map(20, 4);
+
+    for start in 0..20 {
+        assert_eq!(nearest_neighbour(&map, start)[0], start);
+    }
+}
+
+#[test]
+fn double_tree_is_at_most_twice_the_optimum() {
+    for seed in 0..10 {
+        let map = random_map(12, seed);
+        let optimum = held_karp(&map).unwrap().length;
+
+        for (name, tour) in tours(&map) {
+            assert!(map.tour_length(&tour) >= optimum - 1e-6, "{}", name);
+        }
+
+        assert!(map.tour_length(&double_tree(&map)) <= 2.0 * optimum + 1e-6);
+    }
+}
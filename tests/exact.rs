@@ -0,0 +1,121 @@
+use luve_rust::colony::Colony;
+use luve_rust::exact::{branch_and_bound, held_karp};
+use luve_rust::local_search::{LocalSearch, LocalSearchScope};
+use luve_rust::map::Map;
+use luve_rust::params::AcoParams;
+use luve_rust::strategy::{MaxMinParams, Strategy};
+
+mod common;
+
+use common::{assert_permutation, random_map};
+
+/// Returns the length of the shortest tour, trying every permutation.
+fn brute_force(map: &Map) -> f64 {
+    fn permute(map: &Map, tour: &mut Vec<usize>, k: usize, best: &mut f64) {
+        if k == tour.len() {
+            *best = best.min(map.tour_length(tour));
+            return
+        }
+
+        for i in k..tour.len() {
+            tour.swap(k, i);
+            permute(map, tour, k + 1, best);
+            tour.swap(k, i);
+        }
+    }
+
+    let mut tour: Vec<usize> = (0..map.cities.len()).collect();
+    let mut best = f64::INFINITY;
+    permute(map, &mut tour, 1, &mut best);
+
+    best
+}
+
+#[test]
+fn exact_solvers_match_brute_force() {
+    for count in 1..=8 {
+        for seed in 0..3 {
+            let map = random_map(count, seed);
+            let optimum = brute_force(&map);
+
+            let dp = held_karp(&map).unwrap();
+            let bnb = branch_and_bound(&map);
+
+            assert_permutation(&dp.tour, count);
+            assert_permutation(&bnb.tour, count);
+            assert!((dp.length - optimum).abs() < 1e-6, "{} cities: {} != {}", count, dp.length, optimum);
+            assert!((bnb.length - optimum).abs() < 1e-6, "{} cities: {} != {}", count, bnb.length, optimum);
+        }
+    }
+}
+
+#[test]
+fn exact_solvers_agree() {
+    for seed in 0..5 {
+        let map = random_map(13, 100 + seed);
+        let dp = held_karp(&map).unwrap();
+        let bnb = branch_and_bound(&map);
+
+        assert!((dp.length - bnb.length).abs() < 1e-6);
+        assert!((dp.length - map.tour_length(&dp.tour)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn held_karp_rejects_large_maps() {
+    assert!(held_karp(&random_map(21, 0)).is_none());
+}
+
+#[test]
+fn colony_reaches_optimum_on_small_maps() {
+    let strategies = [Strategy::AntSystem, Strategy::MaxMin(MaxMinParams::default())];
+
+    for seed in 0..8 {
+        let map = random_map(12, 200 + seed);
+        let optimum = held_karp(&map).unwrap().length;
+
+        for strategy in strategies.iter() {
+            let params = AcoParams {
+                iterations: 300,
+                seed: Some(seed),
+                target_length: Some(optimum + 1e-6),
+                strategy: strategy.clone(),
+                ..AcoParams::default()
+            };
+
+            let result = Colony::new(random_map(12, 200 + seed), params).unwrap().run();
+
+            assert!(
+                result.best_length < optimum + 1e-6,
+                "seed {} with {:?}: {} instead of {}",
+                seed,
+                strategy,
+                result.best_length,
+                optimum
+            );
+        }
+    }
+}
+
+#[test]
+fn colony_with_local_search_reaches_optimum() {
+    for seed in 0..5 {
+        let map = random_map(18, 300 + seed);
+        let optimum = held_karp(&map).unwrap().length;
+
+        let params = AcoParams {
+            iterations: 200,
+            seed: Some(seed),
+            target_length: Some(optimum + 1e-6),
+            local_search: LocalSearch {
+                scope: LocalSearchScope::EveryAnt,
+                ..LocalSearch::default()
+            },
+            ..AcoParams::default()
+        };
+
+        let result = Colony::new(map, params).unwrap().run();
+
+        assert!(result.best_length < optimum + 1e-6, "seed {}: {} instead of {}", seed, result.best_length, optimum);
+    }
+}
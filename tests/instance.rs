@@ -0,0 +1,180 @@
+use luve_rust::instance::{load_instance, parse_csv, parse_json, parse_solomon};
+
+/// Returns the positions of the cities of an instance.
+fn positions(map: &luve_rust::map::Map) -> Vec<(f32, f32)> {
+    map.cities.iter().map(|city| (city.position.x, city.position.y)).collect()
+}
+
+#[test]
+fn csv_accepts_headers_comments_and_ids() {
+    let instance = parse_csv("x,y\n# a comment\n\n0,0\n3;4\n").unwrap();
+
+    assert_eq!(positions(&instance.map), vec![(0.0, 0.0), (3.0, 4.0)]);
+
+    let instance = parse_csv("id,x,y\n1,1.5,2\n2,-3,4e1\n").unwrap();
+
+    assert_eq!(positions(&instance.map), vec![(1.5, 2.0), (-3.0, 40.0)]);
+}
+
+#[test]
+fn csv_errors_give_the_line() {
+    let error = parse_csv("0,0\n1,2,3,4\n").err().unwrap();
+
+    assert_eq!(error.message, "line 2: 2 or 3 columns expected, 4 found");
+
+    let error = parse_csv("0,0\n1,two\n").err().unwrap();
+
+    assert_eq!(error.message, "line 2: invalid number `two`");
+    assert_eq!(parse_csv("# nothing\n").err().unwrap().message, "the instance contains no city");
+}
+
+#[test]
+fn json_reads_arrays_and_objects() {
+    let instance = parse_json("[[0, 0], {\"x\": 3, \"y\": 4}]").unwrap();
+
+    assert_eq!(positions(&instance.map), vec![(0.0, 0.0), (3.0, 4.0)]);
+    assert_eq!(instance.map.distance(0, 1), 5.0);
+}
+
+#[test]
+fn json_skips_nested_values() {
+    let content = r#"
+        {
+            "name": "nested",
+            "meta": {"tags": ["a", {"deep": [true, false, null]}], "empty": {}, "none": []},
+            "cities": [{"id": 1, "x": 1, "y": 2, "extra": {"z": [3]}}, [5, 6]],
+            "comment": "two cities"
+        }
+    "#;
+
+    let instance = parse_json(content).unwrap();
+
+    assert_eq!(instance.name, "nested");
+    assert_eq!(instance.comment, "two cities");
+    assert_eq!(positions(&instance.map), vec![(1.0, 2.0), (5.0, 6.0)]);
+}
+
+#[test]
+fn json_reads_escapes_and_numbers() {
+    let content = r#"{"name": "a \"b\" \\ c\/d\n\u00e9\t", "cities": [[-1.5e2, 0.25], [1E+1, -0], [12, 3e-1]]}"#;
+    let instance = parse_json(content).unwrap();
+
+    assert_eq!(instance.name, "a \"b\" \\ c/d\né\t");
+    assert_eq!(positions(&instance.map), vec![(-150.0, 0.25), (10.0, 0.0), (12.0, 0.3)]);
+}
+
+#[test]
+fn json_errors_give_the_position() {
+    let cases = [
+        ("[[0, 0],\n [1, 2}", "line 2, column 7: `,` or `]` is expected"),
+        ("{\"cities\" [[0, 0]]}", "line 1, column 11: `:` is expected"),
+        ("[[0, 0]] x", "line 1, column 10: unexpected character after the value"),
+        ("[[0, 0], [1, 2e]]", "line 1, column 14: invalid value"),
+        ("{\"name\": \"abc", "line 1, column 14: unterminated string"),
+        ("{\"name\": \"\\q\", \"cities\": []}", "line 1, column 13: invalid escape sequence"),
+        ("[[0, 0], [1, tru]]", "line 1, column 14: invalid value"),
+        ("{\"cities\": [[0, 0]], 3: 4}", "line 1, column 22: a field name is expected"),
+        ("[[0, 0],\n", "line 2, column 1: unexpected end of the document"),
+    ];
+
+    for &(content, message) in cases.iter() {
+        let error = parse_json(content).err().unwrap();
+
+        assert_eq!(error.kind, "format");
+        assert_eq!(error.message, message, "{:?}", content);
+    }
+}
+
+#[test]
+fn json_nesting_is_limited() {
+    let deep = "[".repeat(200_000);
+    let error = parse_json(&deep).err().unwrap();
+
+    assert_eq!(error.kind, "format");
+    assert_eq!(error.message, "line 1, column 65: arrays and objects are nested too deeply");
+
+    // Deep but still allowed, the error comes from the cities...
+    let nested = format!("{}[0, 0]{}", "[".repeat(63), "]".repeat(63));
+    assert_eq!(parse_json(&nested).err().unwrap().message, "city 0 must be `[x, y]` or `{\"x\": .., \"y\": ..}`");
+}
+
+#[test]
+fn json_checks_the_cities() {
+    assert_eq!(parse_json("{\"name\": \"x\"}").err().unwrap().message, "the `cities` array is missing");
+    assert_eq!(parse_json("{\"cities\": 3}").err().unwrap().message, "`cities` must be an array");
+    assert_eq!(
+        parse_json("[[0, 0], [1]]").err().unwrap().message,
+        "city 1 must be `[x, y]` or `{\"x\": .., \"y\": ..}`"
+    );
+}
+
+const SOLOMON: &str = "\
+TINY01
+
+VEHICLE
+NUMBER     CAPACITY
+   4         50
+
+CUSTOMER
+CUST NO.  XCOORD.   YCOORD.    DEMAND   READY TIME  DUE DATE   SERVICE   TIME
+
+    0      40         50          0          0        300          0
+    1      45         68         10         20         60         10
+    2      45         70         20         25         80         10
+    3      42         66         10         10         50         10
+";
+
+#[test]
+fn solomon_instances_have_a_fleet_and_time_windows() {
+    let instance = parse_solomon(SOLOMON).unwrap();
+    let map = &instance.map;
+
+    assert_eq!(instance.name, "TINY01");
+    assert_eq!(instance.comment, "4 vehicles");
+    assert_eq!(map.cities.len(), 4);
+    assert_eq!(map.fleet.unwrap().depot, 0);
+    assert_eq!(map.fleet.unwrap().capacity, 50.0);
+    assert_eq!(map.cities[2].demand, 20.0);
+    assert_eq!(map.cities[2].service_time, 10.0);
+
+    let window = map.cities[1].time_window.unwrap();
+
+    assert_eq!((window.earliest, window.latest), (20.0, 60.0));
+    assert_eq!(map.cities[0].time_window.unwrap().latest, 300.0);
+}
+
+#[test]
+fn solomon_errors() {
+    let skipped = SOLOMON.replace("    2      45", "    5      45");
+
+    assert_eq!(parse_solomon(&skipped).err().unwrap().message, "line 12: customer 2 expected, 5 found");
+    assert!(parse_solomon("TINY\n\nCUSTOMER\n0 1 2 3 4 5 6\n").is_err());
+}
+
+#[test]
+fn solomon_is_detected_by_its_headers() {
+    let directory = std::env::temp_dir();
+    let solomon = directory.join("luve_rust_tiny01.txt");
+    let tsplib = directory.join("luve_rust_square.txt");
+
+    std::fs::write(&solomon, SOLOMON).unwrap();
+    std::fs::write(
+        &tsplib,
+        "NAME: square\nTYPE: TSP\nDIMENSION: 4\nEDGE_WEIGHT_TYPE: EUC_2D\nNODE_COORD_SECTION\n\
+         1 0 0\n2 0 10\n3 10 10\n4 10 0\nEOF\n",
+    )
+    .unwrap();
+
+    let instance = load_instance(&solomon.to_string_lossy()).unwrap();
+
+    assert!(instance.map.fleet.is_some());
+    assert!(instance.map.has_time_windows());
+
+    let instance = load_instance(&tsplib.to_string_lossy()).unwrap();
+
+    assert_eq!(instance.name, "square");
+    assert_eq!(instance.map.tour_length(&[0, 1, 2, 3]), 40.0);
+
+    let _ = std::fs::remove_file(solomon);
+    let _ = std::fs::remove_file(tsplib);
+}
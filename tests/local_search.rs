@@ -0,0 +1,80 @@
+use luve_rust::local_search::{or_opt, segment_insertion, two_opt, LocalSearch};
+
+mod common;
+
+use common::{assert_permutation, random_map, random_tour};
+
+#[test]
+fn moves_keep_a_permutation_and_never_lengthen_the_tour() {
+    for seed in 0..10 {
+        let map = random_map(60, seed);
+        let neighbours = map.nearest_neighbours(10);
+
+        for step in 0..3 {
+            let mut tour = random_tour(&map, seed);
+            let before = map.tour_length(&tour);
+
+            let improved = match step {
+                0 => two_opt(&map, &mut tour, &neighbours),
+                1 => or_opt(&map, &mut tour, &neighbours),
+                _ => segment_insertion(&map, &mut tour, &neighbours, 30),
+            };
+
+            assert_permutation(&tour, 60);
+            assert!(improved);
+            assert!(map.tour_length(&tour) < before);
+        }
+    }
+}
+
+#[test]
+fn improve_keeps_the_first_city() {
+    let map = random_map(50, 4);
+    let neighbours = map.nearest_neighbours(8);
+    let local_search = LocalSearch { segment_insertion: true, ..LocalSearch::default() };
+
+    let mut tour = random_tour(&map, 4);
+    let first = tour[0];
+    let before = map.tour_length(&tour);
+
+    assert!(local_search.improve(&map, &neighbours, &mut tour));
+    assert_permutation(&tour, 50);
+    assert_eq!(tour[0], first);
+    assert!(map.tour_length(&tour) < before);
+
+    // A tour that can't be improved is left as is.
+    let optimum = tour.clone();
+
+    assert!(!local_search.improve(&map, &neighbours, &mut tour));
+    assert_eq!(tour, optimum);
+}
+
+#[test]
+fn two_opt_reaches_a_crossing_free_tour() {
+    for seed in 0..5 {
+        let map = random_map(40, seed);
+        let n = map.cities.len();
+
+        // Every city is a neighbour, so no improving 2-opt move is left.
+        let neighbours = map.nearest_neighbours(n - 1);
+        let mut tour = random_tour(&map, seed);
+
+        two_opt(&map, &mut tour, &neighbours);
+        assert_permutation(&tour, n);
+
+        for i in 0..n {
+            for j in i + 2..n {
+                let (a, b) = (tour[i], tour[i + 1]);
+                let (c, d) = (tour[j], tour[(j + 1) % n]);
+
+                if d == a {
+                    continue
+                }
+
+                let exchange = map.distance(a, c) + map.distance(b, d) - map.distance(a, b) - map.distance(c, d);
+
+                assert!(exchange > -1e-6, "the edges {}-{} and {}-{} can still be exchanged", a, b, c, d);
+            }
+        }
+    }
+}
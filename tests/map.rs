@@ -0,0 +1,72 @@
+mod common;
+
+use common::rectangle;
+
+#[test]
+fn evaporation_keeps_one_minus_rho() {
+    let mut map = rectangle();
+
+    map.evaporate(0.25);
+
+    assert!(map.pheromones.iter().flatten().all(|&pheromone| pheromone == 0.75));
+
+    map.evaporate(0.5);
+
+    assert!(map.pheromones.iter().flatten().all(|&pheromone| pheromone == 0.375));
+}
+
+#[test]
+fn deposit_is_q_over_the_length_in_both_directions() {
+    let mut map = rectangle();
+
+    assert_eq!(map.tour_length(&[0, 1, 2, 3]), 14.0);
+
+    map.evaporate(0.25);
+    map.deposit(&[0, 1, 2, 3], 7.0);
+
+    // Each path of the tour gets 7 / 14, the diagonals keep what is left.
+    for &(from, to) in [(0, 1), (1, 2), (2, 3), (3, 0)].iter() {
+        assert_eq!(map.pheromones[from][to], 1.25);
+        assert_eq!(map.pheromones[to][from], 1.25);
+    }
+
+    assert_eq!(map.pheromones[0][2], 0.75);
+    assert_eq!(map.pheromones[1][3], 0.75);
+}
+
+#[test]
+fn shorter_tours_deposit_more() {
+    let mut map = rectangle();
+
+    map.deposit(&[0, 2, 1, 3], 1.0);
+
+    let crossing = map.pheromones[0][2] - 1.0;
+    let mut map = rectangle();
+
+    map.deposit(&[0, 1, 2, 3], 1.0);
+
+    assert!((crossing - 1.0 / 18.0).abs() < 1e-12);
+    assert!(map.pheromones[0][1] - 1.0 > crossing);
+}
+
+#[test]
+fn best_path_follows_the_pheromones_of_the_current_city() {
+    let mut map = rectangle();
+
+    for &(from, to, pheromone) in [(0, 1, 5.0), (1, 3, 4.0), (0, 2, 3.0)].iter() {
+        map.pheromones[from][to] = pheromone;
+        map.pheromones[to][from] = pheromone;
+    }
+
+    // From the first city's row alone, the path would go to 2 after 1.
+    assert_eq!(map.best_path(0), vec![0, 1, 3, 2]);
+    assert_eq!(map.best_path(2), vec![2, 0, 1, 3]);
+}
+
+#[test]
+fn best_path_breaks_ties_with_the_distance() {
+    let map = rectangle();
+
+    assert_eq!(map.best_path(0), vec![0, 1, 2, 3]);
+    assert_eq!(map.best_path(3), vec![3, 2, 1, 0]);
+}
@@ -0,0 +1,73 @@
+use luve_rust::colony::Colony;
+use luve_rust::local_search::{LocalSearch, LocalSearchScope};
+use luve_rust::params::AcoParams;
+use luve_rust::strategy::{AcsParams, ElitistParams, MaxMinParams, RankBasedParams, Strategy};
+
+mod common;
+
+use common::random_map;
+
+/// Returns the kind of the error of some parameters, `None` if they are valid.
+fn error(params: AcoParams) -> Option<String> {
+    params.validate().err().map(|e| e.kind)
+}
+
+#[test]
+fn default_parameters_are_valid() {
+    assert_eq!(error(AcoParams::default()), None);
+}
+
+#[test]
+fn invalid_parameters_are_rejected() {
+    let default = AcoParams::default;
+    let max_min = |max_min: MaxMinParams| AcoParams { strategy: Strategy::MaxMin(max_min), ..default() };
+    let acs = |acs: AcsParams| AcoParams { strategy: Strategy::AntColonySystem(acs), ..default() };
+    let cases = vec![
+        (AcoParams { alpha: -1.0, ..default() }, "alpha"),
+        (AcoParams { alpha: f64::INFINITY, ..default() }, "alpha"),
+        (AcoParams { beta: f64::NAN, ..default() }, "beta"),
+        (AcoParams { rho: 0.0, ..default() }, "rho"),
+        (AcoParams { rho: 1.5, ..default() }, "rho"),
+        (AcoParams { rho: f64::NAN, ..default() }, "rho"),
+        (AcoParams { q: 0.0, ..default() }, "q"),
+        (AcoParams { q: f64::INFINITY, ..default() }, "q"),
+        (AcoParams { ant_count: 0, ..default() }, "ant_count"),
+        (AcoParams { iterations: 0, ..default() }, "iterations"),
+        (AcoParams { target_length: Some(f64::NAN), ..default() }, "target_length"),
+        (AcoParams { stagnation_limit: Some(0), ..default() }, "stagnation_limit"),
+        (AcoParams { initial_pheromone: -0.5, ..default() }, "initial_pheromone"),
+        (AcoParams { q0: 1.5, ..default() }, "q0"),
+        (AcoParams { q0: -0.1, ..default() }, "q0"),
+        (
+            AcoParams {
+                local_search: LocalSearch { scope: LocalSearchScope::EveryAnt, neighbours: 0, ..LocalSearch::default() },
+                ..default()
+            },
+            "local_search",
+        ),
+        (AcoParams { strategy: Strategy::Elitist(ElitistParams { weight: -1.0 }), ..default() }, "weight"),
+        (AcoParams { strategy: Strategy::RankBased(RankBasedParams { width: 0 }), ..default() }, "width"),
+        (max_min(MaxMinParams { p_best: 0.0, ..MaxMinParams::default() }), "p_best"),
+        (max_min(MaxMinParams { p_best: 1.0, ..MaxMinParams::default() }), "p_best"),
+        (max_min(MaxMinParams { lambda: 2.0, ..MaxMinParams::default() }), "lambda"),
+        (acs(AcsParams { q0: 1.1, ..AcsParams::default() }), "q0"),
+        (acs(AcsParams { xi: 0.0, ..AcsParams::default() }), "xi"),
+    ];
+
+    for (params, kind) in cases {
+        assert_eq!(error(params.clone()), Some(String::from(kind)), "{:?}", params);
+        assert_eq!(Colony::new(random_map(5, 1), params).err().unwrap().kind, kind);
+    }
+}
+
+#[test]
+fn range_bounds_are_accepted() {
+    assert_eq!(error(AcoParams { rho: 1.0, ..AcoParams::default() }), None);
+    assert_eq!(error(AcoParams { q0: 0.0, ..AcoParams::default() }), None);
+    assert_eq!(error(AcoParams { q0: 1.0, ..AcoParams::default() }), None);
+    assert_eq!(error(AcoParams { alpha: 0.0, beta: 0.0, ..AcoParams::default() }), None);
+
+    let acs = AcsParams { q0: 1.0, xi: 1.0 };
+
+    assert_eq!(error(AcoParams { strategy: Strategy::AntColonySystem(acs), ..AcoParams::default() }), None);
+}
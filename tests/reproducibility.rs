@@ -0,0 +1,124 @@
+use luve_rust::local_search::{LocalSearch, LocalSearchScope};
+use luve_rust::strategy::{AcsParams, MaxMinParams, Strategy};
+use luve_rust::{ant::Ant, colony::Colony, params::AcoParams};
+use rand::rngs::mock::StepRng;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+
+use common::random_map;
+
+fn run(seed: u64) -> (Vec<usize>, f64, Vec<Vec<usize>>) {
+    let params = AcoParams {
+        iterations: 20,
+        seed: Some(seed),
+        ..AcoParams::default()
+    };
+
+    let mut colony = Colony::new(random_map(30, 7), params).unwrap();
+    let result = colony.run();
+    let tours = colony.ants.iter().map(|ant| ant.tour.clone()).collect();
+
+    (result.best_tour, result.best_length, tours)
+}
+
+#[test]
+fn same_seed_gives_same_tours() {
+    let (best_a, length_a, tours_a) = run(42);
+    let (best_b, length_b, tours_b) = run(42);
+
+    assert_eq!(best_a, best_b);
+    assert_eq!(length_a, length_b);
+    assert_eq!(tours_a, tours_b);
+}
+
+#[test]
+fn different_seeds_give_different_tours() {
+    let (_, _, tours_a) = run(1);
+    let (_, _, tours_b) = run(2);
+
+    assert_ne!(tours_a, tours_b);
+}
+
+/// Run a colony with `threads` threads and returns
+/// the best tour and the last tours of the ants.
+fn run_with_threads(strategy: Strategy, scope: LocalSearchScope, threads: usize) -> (Vec<usize>, Vec<Vec<usize>>) {
+    let params = AcoParams {
+        iterations: 15,
+        ant_count: 13,
+        seed: Some(9),
+        threads,
+        strategy,
+        local_search: LocalSearch {
+            scope,
+            ..LocalSearch::default()
+        },
+        ..AcoParams::default()
+    };
+
+    let mut colony = Colony::new(random_map(40, 11), params).unwrap();
+    let result = colony.run();
+    let tours = colony.ants.iter().map(|ant| ant.tour.clone()).collect();
+
+    (result.best_tour, tours)
+}
+
+#[test]
+fn threads_give_same_tours_as_sequential() {
+    let strategies = [
+        Strategy::AntSystem,
+        Strategy::MaxMin(MaxMinParams::default()),
+        Strategy::AntColonySystem(AcsParams::default()),
+    ];
+
+    for strategy in strategies.iter() {
+        for &scope in [LocalSearchScope::None, LocalSearchScope::EveryAnt].iter() {
+            let sequential = run_with_threads(strategy.clone(), scope, 1);
+
+            for &threads in [2, 4, 0].iter() {
+                assert_eq!(sequential, run_with_threads(strategy.clone(), scope, threads));
+            }
+        }
+    }
+}
+
+#[test]
+fn injected_rng_is_used_by_ants() {
+    let map = random_map(20, 3);
+    let params = AcoParams::default();
+
+    let mut a = Ant::new(0);
+    let mut b = Ant::new(0);
+    a.explore_map(&map, &params, &mut StdRng::seed_from_u64(5));
+    b.explore_map(&map, &params, &mut StdRng::seed_from_u64(5));
+
+    assert_eq!(a.tour, b.tour);
+    assert_eq!(a.length, b.length);
+
+    // ... while another generator gives another tour.
+    let mut c = Ant::new(0);
+    c.explore_map(&map, &params, &mut StdRng::seed_from_u64(6));
+
+    assert_ne!(a.tour, c.tour);
+}
+
+#[test]
+fn ants_follow_a_stub_rng() {
+    let map = random_map(20, 3);
+    let params = AcoParams::default();
+
+    // A generator that always draws the lowest value, and one that
+    // always draws the highest value, pick different cities.
+    let mut low = Ant::new(0);
+    let mut high = Ant::new(0);
+    low.explore_map(&map, &params, &mut StepRng::new(0, 0));
+    high.explore_map(&map, &params, &mut StepRng::new(u64::MAX, 0));
+
+    assert_ne!(low.tour, high.tour);
+
+    let mut again = Ant::new(0);
+    again.explore_map(&map, &params, &mut StepRng::new(0, 0));
+
+    assert_eq!(low.tour, again.tour);
+}
@@ -0,0 +1,170 @@
+use cgmath::Vector2;
+use luve_rust::ant::Ant;
+use luve_rust::city::City;
+use luve_rust::map::Map;
+use luve_rust::params::AcoParams;
+use luve_rust::schedule::{schedule, TimeWindow};
+use luve_rust::vrp::{is_feasible, Fleet};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Build a city with a time window and a service time.
+fn city(x: f32, window: Option<(f64, f64)>, service_time: f64) -> City {
+    let mut city = City::with_demand(Vector2::new(x, 0.0), 1.0);
+    city.time_window = window.map(|(earliest, latest)| TimeWindow::new(earliest, latest));
+    city.service_time = service_time;
+    city
+}
+
+/// Build a vehicle routing map with `count` cities at random positions,
+/// the first one is the depot. Each city can be served in time by a
+/// vehicle that goes there straight from the depot.
+fn random_map(count: usize, seed: u64) -> Map {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut cities: Vec<City> = (0..count)
+        .map(|_| {
+            let position = Vector2::new(rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0));
+            City::with_demand(position, rng.gen_range(1.0..10.0))
+        })
+        .collect();
+
+    cities[0].demand = 0.0;
+    cities[0].time_window = Some(TimeWindow::new(0.0, 1000.0));
+
+    let depot = cities[0].position;
+
+    for city in cities.iter_mut().skip(1) {
+        let distance = ((city.position.x - depot.x).powi(2) + (city.position.y - depot.y).powi(2)).sqrt() as f64;
+        let earliest = rng.gen_range(distance..300.0);
+
+        city.time_window = Some(TimeWindow::new(earliest, earliest + 50.0));
+        city.service_time = 10.0;
+    }
+
+    let mut map = Map::new(&cities);
+    map.fleet = Some(Fleet::new(0, 40.0));
+    map
+}
+
+#[test]
+fn schedule_waits_for_the_windows_and_adds_up_the_lateness() {
+    let map = Map::new(&[city(0.0, None, 0.0), city(10.0, Some((20.0, 30.0)), 5.0), city(20.0, Some((0.0, 30.0)), 0.0)]);
+    let schedule = schedule(&map, &[0, 1, 2]);
+
+    let times: Vec<(usize, f64, f64, f64, f64)> = schedule
+        .visits
+        .iter()
+        .map(|visit| (visit.city, visit.arrival, visit.start, visit.waiting, visit.lateness))
+        .collect();
+
+    // 10 units of waiting at the second city, then 5 late at the third one...
+    assert_eq!(
+        times,
+        vec![(0, 0.0, 0.0, 0.0, 0.0), (1, 10.0, 20.0, 10.0, 0.0), (2, 35.0, 35.0, 0.0, 5.0), (0, 55.0, 55.0, 0.0, 0.0)]
+    );
+    assert_eq!(schedule.waiting, 10.0);
+    assert_eq!(schedule.lateness, 5.0);
+    assert_eq!(schedule.late_cities(), vec![2]);
+    assert!(!schedule.is_feasible());
+    assert_eq!(map.tour_cost(&[0, 1, 2]), map.tour_length(&[0, 1, 2]) + 5.0 * map.lateness_penalty);
+}
+
+#[test]
+fn schedule_starts_each_route_from_the_depot() {
+    let mut map = Map::new(&[city(0.0, None, 0.0), city(10.0, Some((20.0, 30.0)), 5.0), city(20.0, Some((0.0, 30.0)), 0.0)]);
+    map.fleet = Some(Fleet::new(0, 10.0));
+
+    let schedule = schedule(&map, &[0, 1, 0, 2]);
+    let arrivals: Vec<(usize, f64)> = schedule.visits.iter().map(|visit| (visit.city, visit.arrival)).collect();
+
+    assert_eq!(arrivals, vec![(0, 0.0), (1, 10.0), (0, 35.0), (2, 20.0), (0, 40.0)]);
+    assert_eq!(schedule.waiting, 10.0);
+    assert!(schedule.is_feasible());
+}
+
+#[test]
+fn schedule_starts_when_the_first_window_opens() {
+    let map = Map::new(&[city(0.0, Some((5.0, 10.0)), 2.0), city(10.0, Some((0.0, 16.0)), 0.0)]);
+    let schedule = schedule(&map, &[0, 1]);
+
+    assert_eq!(schedule.visits[0].start, 5.0);
+    assert_eq!(schedule.visits[1].arrival, 17.0);
+    // Back at the first city at 27, after its window closed, which is not late.
+    assert_eq!(schedule.visits[2].arrival, 27.0);
+    assert_eq!(schedule.lateness, 1.0);
+}
+
+#[test]
+fn a_closed_tour_without_a_fleet_is_not_late_on_its_way_back() {
+    let cities: Vec<City> = (0..4)
+        .map(|i| city(10.0 * i as f32, Some((10.0 * i as f64, 10.0 * i as f64 + 5.0)), 0.0))
+        .collect();
+    let map = Map::new(&cities);
+
+    let served = schedule(&map, &[0, 1, 2, 3]);
+
+    assert_eq!(served.lateness, 0.0);
+    assert!(served.is_feasible());
+    assert_eq!(map.tour_cost(&[0, 1, 2, 3]), map.tour_length(&[0, 1, 2, 3]));
+
+    // Starting from city 1, city 0 is served last at 60 and only its own visit is late.
+    let rotated = schedule(&map, &[1, 2, 3, 0]);
+
+    assert_eq!(rotated.lateness, 55.0);
+    assert_eq!(rotated.late_cities(), vec![0]);
+
+    // With a fleet, the depot still has to be reached in time.
+    let mut map = map;
+    map.fleet = Some(Fleet::new(0, 10.0));
+
+    assert_eq!(schedule(&map, &[0, 1, 2, 3]).lateness, 55.0);
+}
+
+#[test]
+fn ants_serve_every_city_in_time_when_they_can() {
+    let params = AcoParams::default();
+
+    for seed in 0..10 {
+        let map = random_map(30, seed);
+        let fleet = map.fleet.unwrap();
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        for index in 0..10 {
+            let mut ant = Ant::new(index);
+            ant.explore_map(&map, &params, &mut rng);
+
+            assert!(is_feasible(&map, &fleet, &ant.tour));
+            assert!(schedule(&map, &ant.tour).is_feasible(), "{:?}", ant.tour);
+            assert_eq!(ant.length, map.tour_length(&ant.tour));
+        }
+    }
+}
+
+#[test]
+fn only_the_first_city_of_a_route_is_late() {
+    // The last two cities can't be reached in time, even from the depot.
+    let mut map = Map::new(&[
+        city(0.0, Some((0.0, 1000.0)), 0.0),
+        city(10.0, Some((0.0, 100.0)), 0.0),
+        city(50.0, Some((0.0, 5.0)), 0.0),
+        city(52.0, Some((0.0, 5.0)), 0.0),
+    ]);
+    map.fleet = Some(Fleet::new(0, 100.0));
+
+    let params = AcoParams::default();
+    let mut rng = StdRng::seed_from_u64(1);
+
+    for index in 0..20 {
+        let mut ant = Ant::new(index);
+        ant.explore_map(&map, &params, &mut rng);
+
+        let schedule = schedule(&map, &ant.tour);
+
+        assert!(is_feasible(&map, &map.fleet.unwrap(), &ant.tour));
+        assert_eq!(schedule.late_cities().len(), 2);
+
+        for pair in schedule.visits.windows(2).filter(|pair| pair[1].lateness > 0.0) {
+            assert_eq!(pair[0].city, 0, "{:?}", ant.tour);
+        }
+    }
+}
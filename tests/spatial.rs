@@ -0,0 +1,89 @@
+use cgmath::Vector2;
+use luve_rust::spatial::Grid;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Returns the squared distance between two points.
+fn squared_distance(a: Vector2<f32>, b: Vector2<f32>) -> f64 {
+    let (x, y) = (a.x as f64 - b.x as f64, a.y as f64 - b.y as f64);
+    x * x + y * y
+}
+
+/// Check that the grid finds the same nearest points as a search
+/// through every point. Points at the same distance may come in any
+/// order, so the distances are compared.
+fn assert_nearest(points: &[Vector2<f32>], count: usize) {
+    let grid = Grid::new(points);
+
+    for (index, &point) in points.iter().enumerate() {
+        let nearest = grid.nearest(index, count);
+        let mut expected: Vec<f64> = (0..points.len())
+            .filter(|&other| other != index)
+            .map(|other| squared_distance(point, points[other]))
+            .collect();
+
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        expected.truncate(count);
+
+        let found: Vec<f64> = nearest.iter().map(|&other| squared_distance(point, points[other])).collect();
+
+        assert_eq!(found, expected, "point {}", index);
+        assert!(nearest.iter().all(|&other| other != index));
+
+        let mut unique = nearest.clone();
+        unique.sort_unstable();
+        unique.dedup();
+
+        assert_eq!(unique.len(), nearest.len());
+    }
+}
+
+#[test]
+fn nearest_matches_a_brute_force_search() {
+    for seed in 0..5 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let points: Vec<Vector2<f32>> = (0..300)
+            .map(|_| Vector2::new(rng.gen_range(0.0..1000.0), rng.gen_range(0.0..1000.0)))
+            .collect();
+
+        for &count in [1, 5, 20].iter() {
+            assert_nearest(&points, count);
+        }
+    }
+}
+
+#[test]
+fn nearest_matches_a_brute_force_search_on_clusters() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let centers = [(10.0, 10.0), (900.0, 50.0), (500.0, 950.0)];
+    let points: Vec<Vector2<f32>> = (0..240)
+        .map(|i| {
+            let (x, y) = centers[i % 3];
+            Vector2::new(x + rng.gen_range(-5.0..5.0), y + rng.gen_range(-5.0..5.0))
+        })
+        .collect();
+
+    // More neighbours than a cluster holds...
+    for &count in [3, 10, 100].iter() {
+        assert_nearest(&points, count);
+    }
+}
+
+#[test]
+fn nearest_handles_lines_and_duplicates() {
+    let line: Vec<Vector2<f32>> = (0..50).map(|i| Vector2::new(i as f32 * 3.0, 7.0)).collect();
+    let duplicates: Vec<Vector2<f32>> = (0..30).map(|i| Vector2::new((i % 4) as f32, 0.0)).collect();
+
+    assert_nearest(&line, 6);
+    assert_nearest(&duplicates, 8);
+}
+
+#[test]
+fn nearest_returns_at_most_the_other_points() {
+    let points = vec![Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), Vector2::new(5.0, 5.0)];
+    let grid = Grid::new(&points);
+
+    assert_eq!(grid.nearest(0, 10), vec![1, 2]);
+    assert!(grid.nearest(0, 0).is_empty());
+    assert!(Grid::new(&points[..1]).nearest(0, 3).is_empty());
+}
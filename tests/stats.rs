@@ -0,0 +1,145 @@
+use luve_rust::colony::Colony;
+use luve_rust::params::AcoParams;
+use luve_rust::stats::{History, IterationStats};
+use std::time::Duration;
+
+mod common;
+
+use common::random_map;
+
+fn history() -> History {
+    let params = AcoParams { iterations: 12, seed: Some(8), ..AcoParams::default() };
+    let mut colony = Colony::new(random_map(25, 8), params).unwrap();
+
+    colony.run().history
+}
+
+/// Returns the values of a statistic, in the order of the CSV columns.
+fn values(stats: &IterationStats) -> Vec<String> {
+    vec![
+        stats.iteration.to_string(),
+        stats.best_length.to_string(),
+        stats.mean_length.to_string(),
+        stats.worst_length.to_string(),
+        stats.global_best.to_string(),
+        stats.branching_factor.to_string(),
+        stats.reset.to_string(),
+        stats.elapsed.as_secs_f64().to_string(),
+    ]
+}
+
+#[test]
+fn history_has_one_statistic_per_iteration() {
+    let history = history();
+
+    assert_eq!(history.iterations.len(), 12);
+
+    for (i, pair) in history.iterations.windows(2).enumerate() {
+        assert_eq!(pair[0].iteration, i);
+        assert!(pair[1].global_best <= pair[0].global_best);
+        assert!(pair[1].elapsed >= pair[0].elapsed);
+    }
+
+    for stats in history.iterations.iter() {
+        assert!(stats.best_length <= stats.mean_length && stats.mean_length <= stats.worst_length);
+        assert!(stats.global_best <= stats.best_length);
+    }
+}
+
+#[test]
+fn csv_export_matches_the_history() {
+    let history = history();
+    let csv = history.to_csv();
+    let mut lines = csv.lines();
+
+    assert_eq!(
+        lines.next(),
+        Some("iteration,best_length,mean_length,worst_length,global_best,branching_factor,reset,elapsed")
+    );
+
+    let rows: Vec<&str> = lines.collect();
+
+    assert_eq!(rows.len(), history.iterations.len());
+
+    for (row, stats) in rows.iter().zip(history.iterations.iter()) {
+        assert_eq!(row.split(',').map(String::from).collect::<Vec<_>>(), values(stats));
+    }
+}
+
+#[test]
+fn json_export_matches_the_history() {
+    let history = history();
+    let json = history.to_json();
+    let keys = [
+        "iteration",
+        "best_length",
+        "mean_length",
+        "worst_length",
+        "global_best",
+        "branching_factor",
+        "reset",
+        "elapsed",
+    ];
+
+    assert!(json.starts_with('[') && json.trim_end().ends_with(']'));
+
+    let objects: Vec<&str> = json.lines().filter(|line| line.trim_start().starts_with('{')).collect();
+
+    assert_eq!(objects.len(), history.iterations.len());
+
+    for (object, stats) in objects.iter().zip(history.iterations.iter()) {
+        let object = object.trim().trim_end_matches(',').trim_start_matches('{').trim_end_matches('}');
+        let fields: Vec<(&str, &str)> = object
+            .split(", ")
+            .map(|field| field.split_once(": ").unwrap())
+            .collect();
+
+        let expected: Vec<(String, String)> =
+            keys.iter().map(|key| format!("\"{}\"", key)).zip(values(stats)).collect();
+
+        assert_eq!(fields.len(), keys.len());
+
+        for ((key, value), (expected_key, expected_value)) in fields.iter().zip(expected.iter()) {
+            assert_eq!(key, expected_key);
+            assert_eq!(value, expected_value);
+        }
+    }
+}
+
+#[test]
+fn json_writes_infinite_numbers_as_null() {
+    let history = History {
+        iterations: vec![IterationStats {
+            iteration: 0,
+            best_length: 10.0,
+            mean_length: 10.0,
+            worst_length: 10.0,
+            global_best: f64::INFINITY,
+            branching_factor: f64::NAN,
+            reset: false,
+            elapsed: Duration::from_millis(1500),
+        }],
+    };
+
+    let json = history.to_json();
+
+    assert!(json.contains("\"global_best\": null"));
+    assert!(json.contains("\"branching_factor\": null"));
+    assert!(json.contains("\"elapsed\": 1.5"));
+}
+
+#[test]
+fn exports_are_written_to_files() {
+    let history = history();
+    let directory = std::env::temp_dir();
+    let (csv, json) = (directory.join("luve_rust_stats.csv"), directory.join("luve_rust_stats.json"));
+
+    history.save_csv(&csv).unwrap();
+    history.save_json(&json).unwrap();
+
+    assert_eq!(std::fs::read_to_string(&csv).unwrap(), history.to_csv());
+    assert_eq!(std::fs::read_to_string(&json).unwrap(), history.to_json());
+
+    let _ = std::fs::remove_file(csv);
+    let _ = std::fs::remove_file(json);
+}
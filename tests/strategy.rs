@@ -0,0 +1,237 @@
+use luve_rust::ant::Ant;
+use luve_rust::colony::Colony;
+use luve_rust::map::Map;
+use luve_rust::params::AcoParams;
+use luve_rust::strategy::{
+    AcsParams, AntColonySystem, ElitistAntSystem, ElitistParams, Iteration, MaxMinParams, PheromoneUpdate,
+    RankBasedAntSystem, RankBasedParams, Strategy,
+};
+
+mod common;
+
+use common::{random_map, rectangle};
+
+/// Returns an ant that walked a tour of the map.
+fn ant(map: &Map, index: usize, tour: &[usize]) -> Ant {
+    let mut ant = Ant::new(index);
+    ant.tour = tour.to_vec();
+    ant.length = map.tour_length(tour);
+    ant
+}
+
+/// Returns the pheromones of a path, in both directions.
+fn pheromones(map: &Map, from: usize, to: usize) -> (f64, f64) {
+    (map.pheromones[from][to], map.pheromones[to][from])
+}
+
+/// Check that a path has `expected` pheromones in both directions.
+fn assert_pheromones(map: &Map, from: usize, to: usize, expected: f64) {
+    let (there, back) = pheromones(map, from, to);
+
+    assert!((there - expected).abs() < 1e-12, "{} -> {}: {} != {}", from, to, there, expected);
+    assert_eq!(there, back);
+}
+
+/// Returns the iterations where a MAX-MIN colony reset its pheromones.
+fn max_min_resets(seed: u64, iterations: usize) -> Vec<usize> {
+    let params = AcoParams {
+        iterations,
+        seed: Some(seed),
+        strategy: Strategy::MaxMin(MaxMinParams::default()),
+        ..AcoParams::default()
+    };
+
+    let mut colony = Colony::new(random_map(50, seed), params).unwrap();
+    colony.run();
+
+    colony.history.iterations.iter().filter(|stats| stats.reset).map(|stats| stats.iteration).collect()
+}
+
+#[test]
+fn max_min_waits_before_resetting() {
+    let delay = MaxMinParams::default().reset_delay;
+
+    for seed in 0..3 {
+        let resets = max_min_resets(seed, 400);
+
+        assert!(resets.iter().all(|&iteration| iteration >= delay), "{:?}", resets);
+        assert!(!resets.is_empty(), "the trails never converged");
+
+        // The trails need some time to converge again after a reset.
+        for pair in resets.windows(2) {
+            assert!(pair[1] - pair[0] >= delay, "{:?}", resets);
+        }
+    }
+}
+
+#[test]
+fn acs_local_update_moves_the_walked_paths_toward_tau0() {
+    let mut map = rectangle();
+    let params = AcoParams::default();
+    let mut acs = AntColonySystem::new(AcsParams { q0: 0.9, xi: 0.1 });
+
+    map.reset_pheromones(1.0);
+    acs.tau0 = 0.5;
+
+    let walker = ant(&map, 0, &[0, 1, 2, 3]);
+    acs.local_update(&mut map, &walker, &params);
+
+    // (1 - xi) * 1 + xi * tau0 on the walked paths only...
+    for &(from, to) in [(0, 1), (1, 2), (2, 3), (3, 0)].iter() {
+        assert_pheromones(&map, from, to, 0.95);
+    }
+
+    assert_pheromones(&map, 0, 2, 1.0);
+    assert_pheromones(&map, 1, 3, 1.0);
+    assert!(acs.updates_locally());
+}
+
+#[test]
+fn acs_global_update_only_touches_the_best_tour() {
+    let mut map = rectangle();
+    let params = AcoParams { rho: 0.5, q: 7.0, ..AcoParams::default() };
+    let mut acs = AntColonySystem::new(AcsParams::default());
+    let ants = [ant(&map, 0, &[0, 2, 1, 3]), ant(&map, 1, &[0, 1, 3, 2])];
+    let best_tour = [0, 1, 2, 3];
+
+    map.reset_pheromones(1.0);
+
+    let iteration = Iteration {
+        ants: &ants,
+        iteration_best: 1,
+        best_tour: &best_tour,
+        best_length: 14.0,
+        iteration: 0,
+        last_improvement: 0,
+    };
+
+    assert!(!acs.update(&mut map, &iteration, &params));
+
+    // (1 - rho) * 1 + rho * q / L_best on the best tour, nothing elsewhere.
+    for &(from, to) in [(0, 1), (1, 2), (2, 3), (3, 0)].iter() {
+        assert_pheromones(&map, from, to, 0.75);
+    }
+
+    assert_pheromones(&map, 0, 2, 1.0);
+    assert_pheromones(&map, 1, 3, 1.0);
+}
+
+#[test]
+fn acs_only_changes_the_best_tour_in_the_first_iteration() {
+    let params = AcoParams {
+        iterations: 1,
+        ant_count: 5,
+        seed: Some(2),
+        strategy: Strategy::AntColonySystem(AcsParams::default()),
+        ..AcoParams::default()
+    };
+    let mut colony = Colony::new(random_map(30, 2), params).unwrap();
+    let tau0 = colony.map.pheromones[0][1];
+
+    colony.iterate();
+
+    // The local updates keep tau0 on the paths that still have it.
+    let mut best = vec![vec![false; 30]; 30];
+
+    for (from, to) in colony.map.paths(&colony.best_tour) {
+        best[from][to] = true;
+        best[to][from] = true;
+    }
+
+    for (from, row) in best.iter().enumerate() {
+        for (to, &best) in row.iter().enumerate() {
+            if best {
+                assert!(colony.map.pheromones[from][to] > tau0);
+            } else {
+                assert_eq!(colony.map.pheromones[from][to], tau0);
+            }
+        }
+    }
+}
+
+#[test]
+fn elitist_adds_weight_deposits_on_the_best_tour() {
+    let mut map = rectangle();
+    let params = AcoParams { rho: 0.5, q: 1.0, ..AcoParams::default() };
+    let mut elitist = ElitistAntSystem { params: ElitistParams { weight: 2.0 } };
+
+    // The tours are 14 and 18 long, the first one is the best so far.
+    let ants = [ant(&map, 0, &[0, 1, 2, 3]), ant(&map, 1, &[0, 2, 1, 3])];
+    let best_tour = [0, 1, 2, 3];
+
+    map.reset_pheromones(1.0);
+
+    let iteration = Iteration {
+        ants: &ants,
+        iteration_best: 0,
+        best_tour: &best_tour,
+        best_length: 14.0,
+        iteration: 0,
+        last_improvement: 0,
+    };
+
+    assert!(!elitist.update(&mut map, &iteration, &params));
+
+    // Evaporation, a deposit of q / L per ant, and weight * q / L_best.
+    assert_pheromones(&map, 0, 1, 0.5 + 1.0 / 14.0 + 2.0 / 14.0);
+    assert_pheromones(&map, 3, 0, 0.5 + 1.0 / 14.0 + 1.0 / 18.0 + 2.0 / 14.0);
+    assert_pheromones(&map, 0, 2, 0.5 + 1.0 / 18.0);
+}
+
+#[test]
+fn rank_based_weights_the_best_ants_by_their_rank() {
+    let mut map = rectangle();
+    let params = AcoParams { rho: 0.5, q: 1.0, ..AcoParams::default() };
+    let mut rank_based = RankBasedAntSystem { params: RankBasedParams { width: 3 } };
+
+    // The tours are 14, 18 and 16 long, so the ranks are 1, 3 and 2.
+    let ants = [ant(&map, 0, &[0, 1, 2, 3]), ant(&map, 1, &[0, 2, 1, 3]), ant(&map, 2, &[0, 2, 3, 1])];
+    let best_tour = [0, 1, 2, 3];
+
+    map.reset_pheromones(1.0);
+
+    let iteration = Iteration {
+        ants: &ants,
+        iteration_best: 0,
+        best_tour: &best_tour,
+        best_length: 14.0,
+        iteration: 0,
+        last_improvement: 0,
+    };
+
+    assert!(!rank_based.update(&mut map, &iteration, &params));
+
+    // (w - r) * q / L_r for the w - 1 best ants, w * q / L_gb for the best so far,
+    // and nothing for the ant of rank 3.
+    assert_pheromones(&map, 0, 1, 0.5 + 2.0 / 14.0 + 1.0 / 16.0 + 3.0 / 14.0);
+    assert_pheromones(&map, 1, 2, 0.5 + 2.0 / 14.0 + 3.0 / 14.0);
+    assert_pheromones(&map, 2, 3, 0.5 + 2.0 / 14.0 + 1.0 / 16.0 + 3.0 / 14.0);
+    assert_pheromones(&map, 3, 0, 0.5 + 2.0 / 14.0 + 3.0 / 14.0);
+    assert_pheromones(&map, 0, 2, 0.5 + 1.0 / 16.0);
+    assert_pheromones(&map, 1, 3, 0.5 + 1.0 / 16.0);
+}
+
+#[test]
+fn rank_based_with_a_width_of_one_only_rewards_the_best_tour() {
+    let mut map = rectangle();
+    let params = AcoParams { rho: 0.5, q: 1.0, ..AcoParams::default() };
+    let mut rank_based = RankBasedAntSystem { params: RankBasedParams { width: 1 } };
+    let ants = [ant(&map, 0, &[0, 2, 1, 3])];
+    let best_tour = [0, 1, 2, 3];
+
+    map.reset_pheromones(1.0);
+
+    let iteration = Iteration {
+        ants: &ants,
+        iteration_best: 0,
+        best_tour: &best_tour,
+        best_length: 14.0,
+        iteration: 3,
+        last_improvement: 1,
+    };
+
+    rank_based.update(&mut map, &iteration, &params);
+
+    assert_pheromones(&map, 0, 1, 0.5 + 1.0 / 14.0);
+    assert_pheromones(&map, 0, 2, 0.5);
+}
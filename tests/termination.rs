@@ -0,0 +1,131 @@
+use luve_rust::colony::{Colony, Termination};
+use luve_rust::observer::{Control, Observer};
+use luve_rust::params::AcoParams;
+use luve_rust::stats::IterationStats;
+use std::time::{Duration, Instant};
+
+mod common;
+
+use common::random_map;
+
+fn colony(params: AcoParams) -> Colony {
+    Colony::new(random_map(20, 5), AcoParams { seed: Some(5), ..params }).unwrap()
+}
+
+/// Sleep at the end of each iteration.
+struct Sleepy(Duration);
+
+impl Observer for Sleepy {
+    fn iteration_ended(&mut self, _colony: &Colony, _stats: &IterationStats) -> Control {
+        std::thread::sleep(self.0);
+        Control::Continue
+    }
+}
+
+/// Stop the colony after some iterations.
+struct StopAfter(usize);
+
+impl Observer for StopAfter {
+    fn iteration_ended(&mut self, colony: &Colony, _stats: &IterationStats) -> Control {
+        if colony.iteration >= self.0 {
+            Control::Stop
+        } else {
+            Control::Continue
+        }
+    }
+}
+
+#[test]
+fn stops_after_the_iterations() {
+    let result = colony(AcoParams { iterations: 7, ..AcoParams::default() }).run();
+
+    assert_eq!(result.termination, Some(Termination::Iterations));
+    assert_eq!(result.iterations, 7);
+    assert_eq!(result.history.iterations.len(), 7);
+}
+
+#[test]
+fn time_limit_counts_the_wall_clock_time() {
+    let params = AcoParams {
+        iterations: 40,
+        time_limit: Some(Duration::from_millis(300)),
+        ..AcoParams::default()
+    };
+
+    let mut colony = colony(params);
+    colony.add_observer(Box::new(Sleepy(Duration::from_millis(50))));
+
+    let start = Instant::now();
+    let result = colony.run();
+    let wall_clock = start.elapsed();
+
+    assert_eq!(result.termination, Some(Termination::TimeLimit));
+    assert!(result.iterations < 40);
+    assert!(wall_clock < Duration::from_millis(1000), "{:?}", wall_clock);
+    assert!(colony.elapsed() >= Duration::from_millis(300));
+
+    // The clock of the colony starts after `start`, and keeps running.
+    assert!(colony.elapsed() <= start.elapsed());
+
+    // The time of each iteration includes the observers.
+    let last = result.history.iterations.last().unwrap().elapsed;
+
+    assert!(last >= Duration::from_millis(50) * (result.iterations as u32 - 1));
+}
+
+#[test]
+fn stops_once_the_target_length_is_reached() {
+    let first = colony(AcoParams { iterations: 1, ..AcoParams::default() }).run();
+    let params = AcoParams {
+        iterations: 100,
+        target_length: Some(first.best_length),
+        ..AcoParams::default()
+    };
+
+    let result = colony(params).run();
+
+    assert_eq!(result.termination, Some(Termination::TargetLength));
+    assert_eq!(result.iterations, 1);
+}
+
+#[test]
+fn target_length_wins_over_the_iterations() {
+    let params = AcoParams {
+        iterations: 1,
+        target_length: Some(f64::INFINITY),
+        ..AcoParams::default()
+    };
+
+    assert_eq!(colony(params).run().termination, Some(Termination::TargetLength));
+}
+
+#[test]
+fn stops_when_stagnating() {
+    let params = AcoParams {
+        iterations: 10_000,
+        stagnation_limit: Some(5),
+        ..AcoParams::default()
+    };
+
+    let mut colony = colony(params);
+    let result = colony.run();
+
+    assert_eq!(result.termination, Some(Termination::Stagnation));
+
+    // The best tour wasn't improved in the last 5 iterations.
+    let history = &result.history.iterations;
+    let last = history.len() - 1;
+
+    assert!(history[last - 5..].iter().all(|stats| stats.global_best == history[last].global_best));
+}
+
+#[test]
+fn observers_can_cancel() {
+    let mut colony = colony(AcoParams { iterations: 50, ..AcoParams::default() });
+    colony.add_observer(Box::new(StopAfter(3)));
+
+    let result = colony.run();
+
+    assert_eq!(result.termination, Some(Termination::Cancelled));
+    assert_eq!(result.iterations, 3);
+}
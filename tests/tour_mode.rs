@@ -0,0 +1,119 @@
+use luve_rust::ant::Ant;
+use luve_rust::colony::Colony;
+use luve_rust::local_search::{LocalSearch, LocalSearchScope};
+use luve_rust::map::TourMode;
+use luve_rust::params::AcoParams;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+mod common;
+
+use common::{assert_permutation, random_map};
+
+/// Check that a tour goes from `start` to `end` through every city.
+fn assert_fixed(tour: &[usize], count: usize, start: usize, end: usize) {
+    assert_permutation(tour, count);
+    assert_eq!(tour.first(), Some(&start));
+    assert_eq!(tour.last(), Some(&end));
+}
+
+#[test]
+fn open_length_leaves_out_the_closing_path() {
+    let mut map = random_map(12, 1);
+    let tour: Vec<usize> = (0..12).rev().collect();
+    let closed = map.tour_length(&tour);
+
+    map.mode = TourMode::Open;
+
+    assert!((map.tour_length(&tour) - (closed - map.distance(0, 11))).abs() < 1e-9);
+    assert_eq!(map.paths(&tour).count(), 11);
+
+    map.mode = TourMode::Fixed { start: 11, end: 0 };
+
+    assert!((map.tour_length(&tour) - (closed - map.distance(0, 11))).abs() < 1e-9);
+}
+
+#[test]
+fn ants_walk_from_start_to_end() {
+    let mut map = random_map(30, 2);
+    map.mode = TourMode::Fixed { start: 7, end: 19 };
+
+    let params = AcoParams::default();
+    let mut rng = StdRng::seed_from_u64(2);
+
+    for index in 0..10 {
+        let mut ant = Ant::new(index);
+        ant.explore_map(&map, &params, &mut rng);
+
+        assert_fixed(&ant.tour, 30, 7, 19);
+        assert!((ant.length - map.tour_length(&ant.tour)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn local_search_keeps_the_ends_of_a_fixed_path() {
+    let local_search = LocalSearch { segment_insertion: true, ..LocalSearch::default() };
+
+    for seed in 0..10 {
+        let mut map = random_map(40, seed);
+        map.mode = TourMode::Fixed { start: 3, end: 31 };
+
+        let neighbours = map.nearest_neighbours(10);
+        let mut tour: Vec<usize> = (0..40).filter(|&city| city != 3 && city != 31).collect();
+        tour.shuffle(&mut StdRng::seed_from_u64(seed));
+        tour.insert(0, 3);
+        tour.push(31);
+
+        let before = map.tour_length(&tour);
+
+        assert!(local_search.improve(&map, &neighbours, &mut tour));
+        assert_fixed(&tour, 40, 3, 31);
+        assert!(map.tour_length(&tour) < before);
+    }
+}
+
+#[test]
+fn local_search_never_lengthens_an_open_path() {
+    let local_search = LocalSearch::default();
+
+    for seed in 0..10 {
+        let mut map = random_map(40, seed);
+        map.mode = TourMode::Open;
+
+        let neighbours = map.nearest_neighbours(10);
+        let mut tour: Vec<usize> = (0..40).collect();
+        tour.shuffle(&mut StdRng::seed_from_u64(seed));
+
+        let before = map.tour_length(&tour);
+
+        assert!(local_search.improve(&map, &neighbours, &mut tour));
+        assert_permutation(&tour, 40);
+        assert!(map.tour_length(&tour) < before);
+    }
+}
+
+#[test]
+fn colony_keeps_the_ends_of_a_fixed_path() {
+    let mut map = random_map(25, 5);
+    map.mode = TourMode::Fixed { start: 24, end: 0 };
+
+    let params = AcoParams {
+        iterations: 10,
+        seed: Some(5),
+        local_search: LocalSearch { scope: LocalSearchScope::EveryAnt, ..LocalSearch::default() },
+        ..AcoParams::default()
+    };
+    let mut colony = Colony::new(map, params).unwrap();
+
+    for _ in 0..10 {
+        colony.iterate();
+
+        for ant in colony.ants.iter() {
+            assert_fixed(&ant.tour, 25, 24, 0);
+        }
+    }
+
+    assert_fixed(&colony.best_tour, 25, 24, 0);
+    assert!((colony.map.tour_length(&colony.best_tour) - colony.best_length).abs() < 1e-9);
+}
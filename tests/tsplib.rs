@@ -0,0 +1,154 @@
+use luve_rust::tsplib::{format_tour, parse_tour, parse_tsp};
+
+const BURMA14: &str = "\
+NAME: burma14
+TYPE: TSP
+COMMENT: 14-Staedte in Burma (Zaw Win)
+DIMENSION: 14
+EDGE_WEIGHT_TYPE: GEO
+EDGE_WEIGHT_FORMAT: FUNCTION
+DISPLAY_DATA_TYPE: COORD_DISPLAY
+NODE_COORD_SECTION
+   1  16.47       96.10
+   2  16.47       94.44
+   3  20.09       92.54
+   4  22.39       93.37
+   5  25.23       97.24
+   6  22.00       96.05
+   7  20.47       97.02
+   8  17.20       96.29
+   9  16.30       97.38
+  10  14.05       98.12
+  11  16.53       97.38
+  12  21.52       95.59
+  13  19.41       97.13
+  14  20.09       94.55
+EOF
+";
+
+const BURMA14_OPT_TOUR: &str = "\
+NAME : burma14.opt.tour
+TYPE : TOUR
+DIMENSION : 14
+TOUR_SECTION
+1 2 14 3 4 5 6 12 7 13 8 11 9 10
+-1
+";
+
+/// Returns a problem of two cities with the given edge weight type.
+fn two_cities(edge_weight_type: &str, a: (f64, f64), b: (f64, f64)) -> String {
+    format!(
+        "TYPE: TSP\nDIMENSION: 2\nEDGE_WEIGHT_TYPE: {}\nNODE_COORD_SECTION\n1 {} {}\n2 {} {}\nEOF\n",
+        edge_weight_type, a.0, a.1, b.0, b.1
+    )
+}
+
+/// Returns the distance between the two cities of `two_cities`.
+fn distance(edge_weight_type: &str, a: (f64, f64), b: (f64, f64)) -> f64 {
+    parse_tsp(&two_cities(edge_weight_type, a, b)).unwrap().map.distance(0, 1)
+}
+
+#[test]
+fn geo_distances_give_the_burma14_optimum() {
+    let instance = parse_tsp(BURMA14).unwrap();
+    let tour = parse_tour(BURMA14_OPT_TOUR).unwrap();
+
+    assert_eq!(instance.name, "burma14");
+    assert_eq!(instance.map.cities.len(), 14);
+    assert_eq!(instance.map.tour_length(&tour), 3323.0);
+}
+
+#[test]
+fn euclidean_distances_follow_the_rounding_rules() {
+    assert_eq!(distance("EUC_2D", (0.0, 0.0), (3.0, 4.0)), 5.0);
+    assert_eq!(distance("EUC_2D", (0.0, 0.0), (1.0, 1.0)), 1.0);
+    assert_eq!(distance("EUC_2D", (0.0, 0.0), (1.0, 1.2)), 2.0);
+
+    assert_eq!(distance("CEIL_2D", (0.0, 0.0), (3.0, 4.0)), 5.0);
+    assert_eq!(distance("CEIL_2D", (0.0, 0.0), (1.0, 1.0)), 2.0);
+}
+
+#[test]
+fn att_distances_round_up() {
+    // sqrt(100 / 10) = 3.16 is rounded up to 4...
+    assert_eq!(distance("ATT", (0.0, 0.0), (10.0, 0.0)), 4.0);
+
+    // ... and sqrt(2500 / 10) = 15.81 to its nearest integer, 16.
+    assert_eq!(distance("ATT", (0.0, 0.0), (30.0, 40.0)), 16.0);
+}
+
+#[test]
+fn explicit_formats_give_the_same_matrix() {
+    let problem = |format: &str, weights: &str| {
+        format!(
+            "TYPE: TSP\nDIMENSION: 4\nEDGE_WEIGHT_TYPE: EXPLICIT\nEDGE_WEIGHT_FORMAT: {}\nEDGE_WEIGHT_SECTION\n{}\nEOF\n",
+            format, weights
+        )
+    };
+
+    let formats = [
+        ("FULL_MATRIX", "0 1 2 3\n1 0 4 5\n2 4 0 6\n3 5 6 0"),
+        ("UPPER_ROW", "1 2 3\n4 5\n6"),
+        ("LOWER_ROW", "1\n2 4\n3 5 6"),
+        ("UPPER_DIAG_ROW", "0 1 2 3\n0 4 5\n0 6\n0"),
+        ("LOWER_DIAG_ROW", "0\n1 0\n2 4 0\n3 5 6 0"),
+    ];
+
+    let full = parse_tsp(&problem(formats[0].0, formats[0].1)).unwrap().map;
+
+    for &(format, weights) in formats.iter().skip(1) {
+        let map = parse_tsp(&problem(format, weights)).unwrap().map;
+
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(map.distance(i, j), full.distance(i, j), "{} ({}, {})", format, i, j);
+            }
+        }
+    }
+
+    assert_eq!(full.distance(2, 3), 6.0);
+    assert_eq!(full.distance(3, 1), 5.0);
+}
+
+#[test]
+fn tours_round_trip() {
+    let tour = vec![0, 1, 13, 2, 3, 4, 5, 11, 6, 12, 7, 10, 8, 9];
+    let content = format_tour("burma14", "length 3323", &tour);
+
+    assert_eq!(parse_tour(&content).unwrap(), tour);
+    assert_eq!(parse_tour(BURMA14_OPT_TOUR).unwrap(), tour);
+}
+
+#[test]
+fn missing_dimension_is_an_error() {
+    let error = parse_tsp("TYPE: TSP\nEDGE_WEIGHT_TYPE: EUC_2D\nEOF\n").err().unwrap();
+
+    assert_eq!(error.kind, "format");
+    assert_eq!(error.message, "DIMENSION is missing");
+
+    // The sections need the dimension to be read.
+    let error = parse_tsp("TYPE: TSP\nNODE_COORD_SECTION\n1 0 0\nEOF\n").err().unwrap();
+
+    assert!(error.message.contains("DIMENSION must be given first"), "{}", error.message);
+}
+
+#[test]
+fn node_out_of_range_is_an_error() {
+    let content = "TYPE: TSP\nDIMENSION: 2\nEDGE_WEIGHT_TYPE: EUC_2D\nNODE_COORD_SECTION\n1 0 0\n3 1 1\nEOF\n";
+    let error = parse_tsp(content).err().unwrap();
+
+    assert_eq!(error.kind, "format");
+    assert!(error.message.contains("invalid node 3"), "{}", error.message);
+}
+
+#[test]
+fn duplicate_city_in_a_tour_is_an_error() {
+    let error = parse_tour("TYPE: TOUR\nDIMENSION: 3\nTOUR_SECTION\n1 2 2\n-1\n").err().unwrap();
+
+    assert_eq!(error.kind, "format");
+    assert_eq!(error.message, "city 2 is invalid or visited twice");
+
+    let error = parse_tour("TYPE: TOUR\nDIMENSION: 3\nTOUR_SECTION\n1 2\n-1\n").err().unwrap();
+
+    assert_eq!(error.message, "the tour visits 2 cities, 3 expected");
+}
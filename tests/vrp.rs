@@ -0,0 +1,185 @@
+use cgmath::Vector2;
+use luve_rust::city::City;
+use luve_rust::local_search::LocalSearch;
+use luve_rust::map::Map;
+use luve_rust::tsplib::parse_tsp;
+use luve_rust::vrp::{improve_routes, is_feasible, load, routes, Fleet};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Build a vehicle routing map with `count` cities at random
+/// positions, the first one is the depot.
+fn random_map(count: usize, seed: u64) -> Map {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut cities: Vec<City> = (0..count)
+        .map(|_| {
+            let position = Vector2::new(rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0));
+            City::with_demand(position, rng.gen_range(1.0..10.0))
+        })
+        .collect();
+
+    cities[0].demand = 0.0;
+
+    let mut map = Map::new(&cities);
+    map.fleet = Some(Fleet::new(0, 25.0));
+    map
+}
+
+/// Returns a feasible giant tour that delivers the cities in a random
+/// order, going back to the depot when the vehicle is full.
+fn random_tour(map: &Map, fleet: &Fleet, seed: u64) -> Vec<usize> {
+    let mut cities: Vec<usize> = (1..map.cities.len()).collect();
+    cities.shuffle(&mut StdRng::seed_from_u64(seed));
+
+    let mut tour = vec![fleet.depot];
+    let mut carried = 0.0;
+
+    for city in cities {
+        if carried + map.cities[city].demand > fleet.capacity {
+            tour.push(fleet.depot);
+            carried = 0.0;
+        }
+
+        tour.push(city);
+        carried += map.cities[city].demand;
+    }
+
+    tour
+}
+
+const TOY: &str = "\
+NAME : toy6
+TYPE : CVRP
+DIMENSION : 6
+EDGE_WEIGHT_TYPE : EUC_2D
+CAPACITY : 30
+NODE_COORD_SECTION
+1 10 10
+2 15 12
+3 50 50
+4 90 10
+5 85 15
+6 88 22
+DEMAND_SECTION
+1 10
+2 20
+3 0
+4 15
+5 5
+6 10
+DEPOT_SECTION
+3
+-1
+EOF
+";
+
+#[test]
+fn cvrplib_instances_have_a_fleet() {
+    let instance = parse_tsp(TOY).unwrap();
+    let fleet = instance.map.fleet.unwrap();
+
+    assert_eq!(instance.name, "toy6");
+    assert_eq!(fleet, Fleet::new(2, 30.0));
+
+    let demands: Vec<f64> = instance.map.cities.iter().map(|city| city.demand).collect();
+
+    assert_eq!(demands, vec![10.0, 20.0, 0.0, 15.0, 5.0, 10.0]);
+    assert_eq!(instance.map.distance(0, 2), 57.0);
+}
+
+#[test]
+fn cvrplib_errors() {
+    let cases = [
+        (TOY.replace("CAPACITY : 30\n", ""), "CAPACITY is missing"),
+        (TOY.replace("DEPOT_SECTION\n3\n", "DEPOT_SECTION\n7\n"), "DEPOT_SECTION: invalid node 7"),
+        (TOY.replace("6 10\nDEPOT", "9 10\nDEPOT"), "DEMAND_SECTION: invalid node 9"),
+        (TOY.replace("3\n-1", "3\n4\n-1"), "DEPOT_SECTION must give a single depot"),
+    ];
+
+    for (content, message) in cases.iter() {
+        assert_eq!(parse_tsp(content).err().unwrap().message, *message);
+    }
+}
+
+#[test]
+fn feasible_tours_deliver_every_city_within_the_capacity() {
+    let map = parse_tsp(TOY).unwrap().map;
+    let fleet = map.fleet.unwrap();
+
+    assert!(is_feasible(&map, &fleet, &[2, 0, 1, 2, 3, 4, 5]));
+    assert!(is_feasible(&map, &fleet, &[2, 5, 4, 3, 2, 1, 0]));
+
+    // Overloaded, a city left out, a city visited twice, not starting at the depot...
+    assert!(!is_feasible(&map, &fleet, &[2, 0, 1, 3, 2, 4, 5]));
+    assert!(!is_feasible(&map, &fleet, &[2, 0, 1, 2, 3, 4]));
+    assert!(!is_feasible(&map, &fleet, &[2, 0, 1, 2, 3, 4, 5, 2, 0]));
+    assert!(!is_feasible(&map, &fleet, &[0, 1, 2, 3, 4, 5]));
+
+    assert_eq!(routes(&[2, 0, 1, 2, 3, 4, 5], 2), vec![vec![2, 0, 1], vec![2, 3, 4, 5]]);
+    assert_eq!(load(&map, &[2, 3, 4, 5]), 30.0);
+}
+
+#[test]
+fn moves_between_routes_keep_the_capacity() {
+    // Only the moves between two routes...
+    let local_search = LocalSearch { two_opt: false, or_opt: false, ..LocalSearch::default() };
+
+    for seed in 0..10 {
+        let map = random_map(50, seed);
+        let fleet = map.fleet.unwrap();
+        let neighbours = map.nearest_neighbours(10);
+        let mut tour = random_tour(&map, &fleet, seed);
+        let before = map.tour_length(&tour);
+
+        assert!(is_feasible(&map, &fleet, &tour));
+        assert!(improve_routes(&local_search, &map, &fleet, &neighbours, &mut tour));
+        assert!(is_feasible(&map, &fleet, &tour));
+        assert!(map.tour_length(&tour) < before);
+    }
+}
+
+#[test]
+fn segment_insertion_improves_each_route() {
+    // Only the segment insertion, inside each route...
+    let local_search = LocalSearch {
+        two_opt: false,
+        or_opt: false,
+        segment_insertion: true,
+        inter_route: false,
+        ..LocalSearch::default()
+    };
+
+    for seed in 0..10 {
+        let map = random_map(50, seed);
+        let fleet = map.fleet.unwrap();
+        let neighbours = map.nearest_neighbours(10);
+        let mut tour = random_tour(&map, &fleet, seed);
+        let before = map.tour_length(&tour);
+        let members = |tour: &[usize]| -> Vec<Vec<usize>> {
+            let mut routes = routes(tour, fleet.depot);
+            routes.iter_mut().for_each(|route| route.sort_unstable());
+            routes
+        };
+        let before_routes = members(&tour);
+
+        assert!(improve_routes(&local_search, &map, &fleet, &neighbours, &mut tour));
+        assert!(is_feasible(&map, &fleet, &tour));
+        assert!(map.tour_length(&tour) < before);
+
+        // The cities stay in their route.
+        assert_eq!(members(&tour), before_routes);
+    }
+}
+
+#[test]
+fn routes_without_candidate_lists_are_left_as_is() {
+    let local_search = LocalSearch::default();
+    let map = random_map(30, 3);
+    let fleet = map.fleet.unwrap();
+    let mut tour = random_tour(&map, &fleet, 3);
+    let before = tour.clone();
+
+    assert!(!improve_routes(&local_search, &map, &fleet, &[], &mut tour));
+    assert_eq!(tour, before);
+}